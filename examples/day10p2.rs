@@ -3,10 +3,14 @@ use lazy_static::lazy_static;
 use std::{
     collections::{HashMap, HashSet},
     fs,
-    path::Path,
+    path::{Path, PathBuf},
 };
 
 use anyhow::Error;
+use aoc2023::{
+    svg::{corners, PolygonSvg},
+    Parser,
+};
 use itertools::Itertools;
 use nom::{character::complete::*, multi::many1, sequence::*, IResult};
 
@@ -37,6 +41,17 @@ lazy_static! {
     ];
 }
 
+#[derive(Parser, Debug)]
+struct Opts {
+    /// write the loop to this file as SVG with its inside filled
+    #[arg(short, long)]
+    svg: Option<PathBuf>,
+
+    /// mark the loop's corners in the SVG
+    #[arg(short, long)]
+    markers: bool,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 enum Tile {
     NorthSouth,
@@ -268,14 +283,27 @@ fn search_inside(data: &Data, path_set: &HashSet<Spot>) -> i32 {
 }
 
 fn main() -> Result<(), Error> {
+    let opts = Opts::parse();
+
     let file = Path::new("data/d10p1.txt");
     let contents = fs::read_to_string(file)?;
     let data = parse_data(&contents);
     let data = data.map_err(|err| err.map_input(|s| s.to_string()))?;
-    assert!(data.0 == "");
+    assert!(data.0.is_empty());
     let data = data.1;
 
-    let (_, path_set) = follow_path(&data);
+    let (path, path_set) = follow_path(&data);
+
+    if let Some(file) = opts.svg {
+        let points = path
+            .iter()
+            .map(|s| (s.col as i64, s.row as i64))
+            .collect_vec();
+        let svg = PolygonSvg::new(&corners(&points))
+            .fill("#ccc")
+            .markers(opts.markers);
+        fs::write(file, svg.render())?;
+    }
 
     debug_println!("path_set: {path_set:?}");
 
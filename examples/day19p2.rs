@@ -1,9 +1,9 @@
 use debug_print::debug_println;
-use itertools::Itertools;
 use std::collections::{HashMap, VecDeque};
 use std::{fs, path::Path};
 
 use anyhow::Error;
+use aoc2023::sections::{SectionError, Sections};
 use nom::character::complete::*;
 use nom::{bytes::complete::tag, multi::*, sequence::*, IResult};
 
@@ -147,13 +147,10 @@ fn parse_filter(i: &str) -> IResult<&str, Filter> {
 
 fn parse_workflow(i: &str) -> IResult<&str, Workflow> {
     let (i, name) = alpha1(i)?;
-    let (i, filters) = terminated(
-        delimited(
-            char('{'),
-            separated_list1(tag(","), parse_filter),
-            char('}'),
-        ),
-        line_ending,
+    let (i, filters) = delimited(
+        char('{'),
+        separated_list1(tag(","), parse_filter),
+        char('}'),
     )(i)?;
 
     let wf = Workflow {
@@ -164,39 +161,42 @@ fn parse_workflow(i: &str) -> IResult<&str, Workflow> {
     Ok((i, wf))
 }
 
-fn parse_data(i: &str) -> IResult<&str, Data> {
-    let parse_part = terminated(
-        delimited(
-            char('{'),
-            nom::sequence::tuple((
-                tag("x="),
-                digit1,
-                tag(",m="),
-                digit1,
-                tag(",a="),
-                digit1,
-                tag(",s="),
-                digit1,
-            )),
-            char('}'),
-        ),
-        line_ending,
-    );
-
-    let (i, workflows) = terminated(many1(parse_workflow), line_ending)(i)?;
-    let (i, parts) = terminated(many1(parse_part), multispace0)(i)?;
-
-    let parts = parts
-        .into_iter()
-        .map(|x| Part {
-            x: x.1.parse::<i32>().unwrap(),
-            m: x.3.parse::<i32>().unwrap(),
-            a: x.5.parse::<i32>().unwrap(),
-            s: x.7.parse::<i32>().unwrap(),
-        })
-        .collect_vec();
+fn parse_part(i: &str) -> IResult<&str, Part> {
+    let (i, x) = delimited(
+        char('{'),
+        nom::sequence::tuple((
+            tag("x="),
+            digit1,
+            tag(",m="),
+            digit1,
+            tag(",a="),
+            digit1,
+            tag(",s="),
+            digit1,
+        )),
+        char('}'),
+    )(i)?;
+
+    let part = Part {
+        x: x.1.parse::<i32>().unwrap(),
+        m: x.3.parse::<i32>().unwrap(),
+        a: x.5.parse::<i32>().unwrap(),
+        s: x.7.parse::<i32>().unwrap(),
+    };
+
+    Ok((i, part))
+}
+
+fn parse_data(i: &str) -> Result<Data, SectionError> {
+    let sections = Sections::new(i);
+    let workflows = sections
+        .get(0)?
+        .parse_with(separated_list1(line_ending, parse_workflow))?;
+    let parts = sections
+        .get(1)?
+        .parse_with(separated_list1(line_ending, parse_part))?;
     let data = Data { workflows, parts };
-    Ok((i, data))
+    Ok(data)
 }
 
 fn process_part_range_to_accepted(
@@ -228,10 +228,7 @@ fn process_part_range_to_accepted(
 fn main() -> Result<(), Error> {
     let file = Path::new("data/d19p1.txt");
     let contents = fs::read_to_string(file)?;
-    let data = parse_data(&contents);
-    let data = data.map_err(|err| err.map_input(|s| s.to_string()))?;
-    assert!(data.0 == "");
-    let data = data.1;
+    let data = parse_data(&contents)?;
 
     let mut workflow_map = HashMap::new(); //HashMap<&str, Vec<Filter>>
     for workflow in data.workflows {
@@ -1,68 +1,54 @@
-use debug_print::debug_println;
-use std::{fs, path::Path};
+use std::path::Path;
 
 use anyhow::Error;
-use itertools::Itertools;
-use nom::{
-    bytes::complete::tag, character::complete::*, combinator::*, multi::many1, sequence::*, IResult,
+use aoc2023::{
+    day9::{Newton, Sequence},
+    read_as_lines, Parser,
 };
+use num::BigInt;
 
-#[derive(Debug)]
-struct Data {
-    lines: Vec<Vec<i32>>,
-}
-
-fn parse_i32(i: &str) -> IResult<&str, i32> {
-    let (i, n) = map_res(recognize(preceded(opt(tag("-")), digit1)), |s: &str| {
-        s.parse::<i32>()
-    })(i)?;
-
-    Ok((i, n))
-}
+#[derive(Parser, Debug)]
+struct Opts {
+    /// how many values to extrapolate before the start of each row
+    #[arg(short, long, default_value_t = 1)]
+    steps: usize,
 
-fn parse_data(i: &str) -> IResult<&str, Data> {
-    let parse_line = terminated(many1(terminated(parse_i32, space0)), multispace0);
-    let (i, lines) = many1(parse_line)(i)?;
-    let data = Data { lines };
-    Ok((i, data))
-}
-
-fn predict(nums: &Vec<i32>) -> i32 {
-    debug_println!("{nums:?}");
-    let mut history = vec![];
-    history.push(nums.first().cloned().unwrap());
-    let mut diff = nums.windows(2).map(|vals| vals[1] - vals[0]).collect_vec();
-    debug_println!("diff: {diff:?}");
-    loop {
-        history.push(diff.first().cloned().unwrap());
-        diff = diff.windows(2).map(|vals| vals[1] - vals[0]).collect_vec();
-
-        debug_println!("diff: {diff:?}");
-
-        if diff.iter().all(|v| *v == 0) {
-            break;
-        }
-    }
-    debug_println!("history: {history:?}");
-
-    history.iter().rev().fold(0, |acc, v| v - acc)
+    /// print the detected degree and predictions for every row
+    #[arg(short, long)]
+    verbose: bool,
 }
 
 fn main() -> Result<(), Error> {
+    let opts = Opts::parse();
+
     let file = Path::new("data/d9p1.txt");
-    let contents = fs::read_to_string(file)?;
-    let data = parse_data(&contents);
-    let data = data.map_err(|err| err.map_input(|s| s.to_string()))?;
-    assert!(data.0 == "");
-    let data = data.1;
+    let lines = read_as_lines(file)?;
 
-    let prediction = data
-        .lines
+    let sequences = lines
         .iter()
-        .map(|line| predict(line) as i64)
-        .sum::<i64>();
+        .map(|s| s.parse::<Sequence>())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut prediction = BigInt::from(0);
+    for (n, seq) in sequences.iter().enumerate() {
+        let Some(fit) = Newton::fit(&seq.values) else {
+            continue;
+        };
+        let values = fit.backward(opts.steps);
+        if opts.verbose {
+            let note = if fit.confirmed() {
+                ""
+            } else {
+                " (unconfirmed)"
+            };
+            println!("row {}: degree {}{note}, {values:?}", n + 1, fit.degree());
+        }
+        if let Some(last) = values.last() {
+            prediction += last;
+        }
+    }
 
-    println!("{prediction:?}");
+    println!("{prediction}");
 
     Ok(())
 }
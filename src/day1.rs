@@ -0,0 +1,104 @@
+use aho_corasick::AhoCorasick;
+use anyhow::{anyhow, Error};
+
+pub const ENGLISH_WORDS: [(&str, u8); 9] = [
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+/// A digit found in a line along with the byte range it was spelled across.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DigitMatch {
+    pub value: u8,
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Calibration {
+    pub first: DigitMatch,
+    pub last: DigitMatch,
+}
+
+impl Calibration {
+    pub fn value(&self) -> u32 {
+        self.first.value as u32 * 10 + self.last.value as u32
+    }
+}
+
+/// Finds the digits `0`-`9` and any spelled-out words from its table in a
+/// single pass over each line. Overlapping matches are reported, so `twone`
+/// yields both 2 and 1.
+pub struct Extractor {
+    automaton: AhoCorasick,
+    values: Vec<u8>,
+}
+
+impl Extractor {
+    pub fn new<S: AsRef<str>>(words: &[(S, u8)]) -> Result<Extractor, Error> {
+        let mut patterns = (0..=9).map(|d| d.to_string()).collect::<Vec<_>>();
+        let mut values = (0..=9).collect::<Vec<_>>();
+        for (word, value) in words {
+            let word = word.as_ref();
+            if word.is_empty() {
+                return Err(anyhow!("empty word in table"));
+            }
+            patterns.push(word.to_owned());
+            values.push(*value);
+        }
+
+        let automaton = AhoCorasick::new(patterns)?;
+        Ok(Extractor { automaton, values })
+    }
+
+    pub fn digits() -> Extractor {
+        Extractor::new::<&str>(&[]).unwrap()
+    }
+
+    pub fn matches<'a>(&'a self, line: &'a str) -> impl Iterator<Item = DigitMatch> + 'a {
+        self.automaton
+            .find_overlapping_iter(line)
+            .map(|m| DigitMatch {
+                value: self.values[m.pattern().as_usize()],
+                start: m.start(),
+                end: m.end(),
+            })
+    }
+
+    /// The earliest and latest starting matches in `line`, if there are any.
+    pub fn extract(&self, line: &str) -> Option<Calibration> {
+        let mut matches = self.matches(line);
+        let first = matches.next()?;
+        let (first, last) = matches.fold((first, first), |(first, last), m| {
+            let first = if m.start < first.start { m } else { first };
+            let last = if m.start > last.start { m } else { last };
+            (first, last)
+        });
+        Some(Calibration { first, last })
+    }
+}
+
+/// Parses a word table with one `word digit` pair per line.
+pub fn parse_word_table(s: &str) -> Result<Vec<(String, u8)>, Error> {
+    s.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let (word, value) = line
+                .split_once(char::is_whitespace)
+                .ok_or(anyhow!("expected 'word digit' in {line:?}"))?;
+            let value = value.trim().parse::<u8>()?;
+            if value > 9 {
+                return Err(anyhow!("{value} is not a digit in {line:?}"));
+            }
+            Ok((word.to_owned(), value))
+        })
+        .collect()
+}
@@ -0,0 +1,176 @@
+use std::collections::HashSet;
+
+use nom::{character::complete::*, multi::many1, sequence::*, IResult};
+
+#[derive(Debug, Eq, PartialEq, Hash, Default, Clone, Copy)]
+pub struct Spot {
+    pub row: i32,
+    pub col: i32,
+}
+
+#[derive(Debug)]
+pub struct Data {
+    pub num_rows: i32,
+    pub num_cols: i32,
+    pub galaxies: Vec<Spot>,
+}
+
+pub fn parse_data(i: &str) -> IResult<&str, Data> {
+    let parse_line = terminated(many1(one_of(".#")), multispace0);
+    let (i, lines) = many1(parse_line)(i)?;
+    let mut galaxies = vec![];
+    let num_rows = lines.len() as i32;
+    let num_cols = lines[0].len() as i32;
+    lines.into_iter().enumerate().for_each(|(row, line)| {
+        line.into_iter().enumerate().for_each(|(col, tile)| {
+            let spot = Spot {
+                row: row as i32,
+                col: col as i32,
+            };
+            if tile == '#' {
+                galaxies.push(spot);
+            }
+        });
+    });
+    let data = Data {
+        galaxies,
+        num_rows,
+        num_cols,
+    };
+    Ok((i, data))
+}
+
+/// Rows and columns without any galaxies in them.
+pub fn find_gaps(data: &Data) -> (HashSet<i32>, HashSet<i32>) {
+    let galaxy_rows = data.galaxies.iter().map(|s| s.row).collect::<HashSet<_>>();
+    let galaxy_cols = data.galaxies.iter().map(|s| s.col).collect::<HashSet<_>>();
+
+    let row_gaps = (0..data.num_rows)
+        .filter(|row| !galaxy_rows.contains(row))
+        .collect();
+    let col_gaps = (0..data.num_cols)
+        .filter(|col| !galaxy_cols.contains(col))
+        .collect();
+
+    (row_gaps, col_gaps)
+}
+
+/// Where each of `coords` ends up once every gap before it has grown to
+/// `factor` rows or columns. `factor` must be at least 1.
+pub fn expand(coords: &[i32], gaps: &HashSet<i32>, factor: u64) -> Vec<u64> {
+    let mut gaps = gaps.iter().copied().collect::<Vec<_>>();
+    gaps.sort_unstable();
+    coords
+        .iter()
+        .map(|&c| c as u64 + (factor - 1) * gaps.partition_point(|&g| g < c) as u64)
+        .collect()
+}
+
+/// Sum of `|a - b|` over all pairs. Once sorted, each value is subtracted
+/// from by everything before it, so a running prefix sum covers every pair.
+fn sum_differences(mut values: Vec<u64>) -> u64 {
+    values.sort_unstable();
+    let mut prefix = 0;
+    let mut total = 0;
+    for (n, v) in values.into_iter().enumerate() {
+        total += v * n as u64 - prefix;
+        prefix += v;
+    }
+    total
+}
+
+/// Sum of the Manhattan distances between every pair of galaxies after
+/// each empty row and column grows to `factor` of them. Rows and columns
+/// are independent, so each axis is summed on its own.
+pub fn sum_of_distances(data: &Data, gaps: &(HashSet<i32>, HashSet<i32>), factor: u64) -> u64 {
+    let rows = data.galaxies.iter().map(|s| s.row).collect::<Vec<_>>();
+    let cols = data.galaxies.iter().map(|s| s.col).collect::<Vec<_>>();
+    sum_differences(expand(&rows, &gaps.0, factor))
+        + sum_differences(expand(&cols, &gaps.1, factor))
+}
+
+/// The galaxies after expansion, numbered by their position in the parsed
+/// galaxy list, for distance queries between them.
+#[derive(Debug, Clone)]
+pub struct Universe {
+    positions: Vec<(u64, u64)>,
+}
+
+impl Universe {
+    pub fn new(galaxies: &[Spot], gaps: &(HashSet<i32>, HashSet<i32>), factor: u64) -> Universe {
+        let rows = galaxies.iter().map(|s| s.row).collect::<Vec<_>>();
+        let cols = galaxies.iter().map(|s| s.col).collect::<Vec<_>>();
+        let positions = expand(&rows, &gaps.0, factor)
+            .into_iter()
+            .zip(expand(&cols, &gaps.1, factor))
+            .collect();
+        Universe { positions }
+    }
+
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// Expanded `(row, col)` of galaxy `id`.
+    pub fn position(&self, id: usize) -> Option<(u64, u64)> {
+        self.positions.get(id).copied()
+    }
+
+    pub fn distance(&self, a: usize, b: usize) -> Option<u64> {
+        let (a, b) = (self.position(a)?, self.position(b)?);
+        Some(a.0.abs_diff(b.0) + a.1.abs_diff(b.1))
+    }
+
+    /// Distance between every pair of galaxies, indexed by id.
+    pub fn distance_matrix(&self) -> Vec<Vec<u64>> {
+        (0..self.len())
+            .map(|a| {
+                (0..self.len())
+                    .map(|b| self.distance(a, b).unwrap())
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// The `k` galaxies closest to `id`, nearest first, ties broken by id.
+    pub fn nearest(&self, id: usize, k: usize) -> Option<Vec<(usize, u64)>> {
+        self.position(id)?;
+        let mut others = (0..self.len())
+            .filter(|&other| other != id)
+            .map(|other| (other, self.distance(id, other).unwrap()))
+            .collect::<Vec<_>>();
+        others.sort_by_key(|&(other, d)| (d, other));
+        others.truncate(k);
+        Some(others)
+    }
+
+    /// The two galaxies furthest apart. Manhattan distance is the larger
+    /// of the spreads along `row + col` and `row - col`, so only the
+    /// extremes of those need looking at.
+    pub fn farthest_pair(&self) -> Option<(usize, usize, u64)> {
+        if self.len() < 2 {
+            return None;
+        }
+        let ids = 0..self.len();
+        let sum = |id: &usize| {
+            let (r, c) = self.positions[*id];
+            r + c
+        };
+        let diff = |id: &usize| {
+            let (r, c) = self.positions[*id];
+            r as i128 - c as i128
+        };
+
+        [
+            (ids.clone().min_by_key(sum)?, ids.clone().max_by_key(sum)?),
+            (ids.clone().min_by_key(diff)?, ids.max_by_key(diff)?),
+        ]
+        .into_iter()
+        .map(|(a, b)| (a.min(b), a.max(b), self.distance(a, b).unwrap()))
+        .max_by_key(|&(_, _, d)| d)
+    }
+}
@@ -0,0 +1,130 @@
+use std::{io::BufRead, str::FromStr};
+
+use anyhow::{anyhow, Error};
+use nom::{character::complete::*, multi::*, IResult};
+
+use crate::input::LineReader;
+
+pub fn parse_grid(i: &str) -> IResult<&str, Vec<Vec<char>>> {
+    separated_list1(line_ending, many1(one_of(".#")))(i)
+}
+
+/// Reads the blank-line separated patterns one block at a time.
+pub fn read_patterns<R: BufRead>(reader: &mut LineReader<R>) -> Result<Vec<Pattern>, Error> {
+    let mut patterns = vec![];
+    while let Some(block) = reader.next_block()? {
+        let pattern = block
+            .parse()
+            .map_err(|err: Error| err.context(format!("pattern {}", patterns.len())))?;
+        patterns.push(pattern);
+    }
+    Ok(patterns)
+}
+
+/// Where a pattern is mirrored: between `n` and `n + 1` columns or rows in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axis {
+    Column(usize),
+    Row(usize),
+}
+
+impl Axis {
+    /// Columns left of the line, or 100 times the rows above it.
+    pub fn summary(&self) -> usize {
+        match self {
+            Axis::Column(n) => *n,
+            Axis::Row(n) => 100 * n,
+        }
+    }
+}
+
+/// A line of reflection that needs exactly `smudges.len()` cells flipped,
+/// given as `(row, col)` on the top or left side of the line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reflection {
+    pub axis: Axis,
+    pub smudges: Vec<(usize, usize)>,
+}
+
+/// A pattern with each row and each column packed into a bitmask, bit `i`
+/// being cell `i` along it, so comparing two lines is a popcount.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    rows: Vec<u128>,
+    cols: Vec<u128>,
+}
+
+impl FromStr for Pattern {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (rest, grid) =
+            parse_grid(s).map_err(|err| anyhow!("{}", err.map_input(|s| s.to_string())))?;
+        if !rest.trim().is_empty() {
+            return Err(anyhow!("unparsed input {rest:?}"));
+        }
+        Pattern::new(&grid).ok_or(anyhow!(
+            "pattern must be rectangular and at most 128 wide and tall"
+        ))
+    }
+}
+
+impl Pattern {
+    pub fn new(grid: &[Vec<char>]) -> Option<Pattern> {
+        let ncols = grid.first()?.len();
+        if grid.len() > 128 || ncols > 128 || grid.iter().any(|row| row.len() != ncols) {
+            return None;
+        }
+
+        let mut rows = vec![0; grid.len()];
+        let mut cols = vec![0; ncols];
+        for (r, line) in grid.iter().enumerate() {
+            for (c, _) in line.iter().enumerate().filter(|(_, &ch)| ch == '#') {
+                rows[r] |= 1 << c;
+                cols[c] |= 1 << r;
+            }
+        }
+        Some(Pattern { rows, cols })
+    }
+
+    /// Mirror lines through `lines` that differ in exactly `k` cells, as the
+    /// position of the line and the differing cells as `(line, offset)`.
+    fn mirrors(lines: &[u128], k: u32) -> Vec<(usize, Vec<(usize, usize)>)> {
+        (1..lines.len())
+            .filter_map(|n| {
+                let mut diffs = 0;
+                let mut smudges = vec![];
+                for (a, b) in (0..n).rev().zip(n..lines.len()) {
+                    let mut xor = lines[a] ^ lines[b];
+                    diffs += xor.count_ones();
+                    if diffs > k {
+                        return None;
+                    }
+                    while xor != 0 {
+                        smudges.push((a, xor.trailing_zeros() as usize));
+                        xor &= xor - 1;
+                    }
+                }
+                (diffs == k).then_some((n, smudges))
+            })
+            .collect()
+    }
+
+    /// Every line of reflection needing exactly `k` smudges fixed, columns
+    /// first.
+    pub fn reflections(&self, k: u32) -> Vec<Reflection> {
+        let cols = Pattern::mirrors(&self.cols, k)
+            .into_iter()
+            .map(|(n, cells)| Reflection {
+                axis: Axis::Column(n),
+                smudges: cells.into_iter().map(|(c, r)| (r, c)).collect(),
+            });
+        let rows = Pattern::mirrors(&self.rows, k)
+            .into_iter()
+            .map(|(n, smudges)| Reflection {
+                axis: Axis::Row(n),
+                smudges,
+            });
+        cols.chain(rows).collect()
+    }
+}
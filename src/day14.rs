@@ -0,0 +1,279 @@
+use std::{
+    collections::HashMap,
+    fmt::{Display, Write},
+    str::FromStr,
+};
+
+use anyhow::{anyhow, Error};
+use itertools::Itertools;
+use nom::{
+    character::{complete::*, streaming::newline},
+    multi::*,
+    sequence::*,
+    IResult,
+};
+
+pub fn parse_grid(i: &str) -> IResult<&str, Vec<Vec<char>>> {
+    let parse_line = terminated(many1(one_of(".#O")), newline);
+    let mut parse_problem = terminated(many1(parse_line), multispace0);
+    parse_problem(i)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dir {
+    North,
+    West,
+    South,
+    East,
+}
+
+impl TryFrom<char> for Dir {
+    type Error = Error;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c.to_ascii_uppercase() {
+            'N' => Ok(Dir::North),
+            'W' => Ok(Dir::West),
+            'S' => Ok(Dir::South),
+            'E' => Ok(Dir::East),
+            _ => Err(anyhow!("unknown direction '{c}'")),
+        }
+    }
+}
+
+impl FromStr for Dir {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "north" => Ok(Dir::North),
+            "west" => Ok(Dir::West),
+            "south" => Ok(Dir::South),
+            "east" => Ok(Dir::East),
+            _ => s
+                .chars()
+                .exactly_one()
+                .map_err(|_| anyhow!("unknown direction '{s}'"))
+                .and_then(Dir::try_from),
+        }
+    }
+}
+
+/// Parses a spin order such as `NWSE`.
+pub fn parse_order(s: &str) -> Result<Vec<Dir>, Error> {
+    s.chars().map(Dir::try_from).collect()
+}
+
+pub const SPIN_CYCLE: [Dir; 4] = [Dir::North, Dir::West, Dir::South, Dir::East];
+
+/// Loads after each spin cycle up to the point the platform starts repeating.
+/// `loads[n]` is the load after `n` cycles and the arrangement after
+/// `start + period` cycles is the same as after `start`.
+#[derive(Debug, Clone)]
+pub struct CycleReport {
+    pub start: usize,
+    pub period: usize,
+    pub loads: Vec<usize>,
+}
+
+impl CycleReport {
+    /// Loads across one period, beginning at cycle `start`.
+    pub fn period_loads(&self) -> &[usize] {
+        &self.loads[self.start..]
+    }
+
+    pub fn load_after(&self, cycles: usize) -> usize {
+        if cycles < self.start {
+            self.loads[cycles]
+        } else {
+            self.loads[self.start + (cycles - self.start) % self.period]
+        }
+    }
+}
+
+/// The rocks on the platform. Round and cube rocks are kept as bitsets over
+/// `row * ncols + col`, and for each tilt direction the cells are split into
+/// runs between cube rocks, ordered from the edge the rocks roll towards, so
+/// tilting is just counting and refilling each run in place.
+#[derive(Debug, Clone)]
+pub struct Platform {
+    nrows: usize,
+    ncols: usize,
+    cubes: Vec<u64>,
+    rounds: Vec<u64>,
+    runs: [Vec<Vec<usize>>; 4],
+}
+
+fn get_bit(bits: &[u64], idx: usize) -> bool {
+    bits[idx / 64] & (1 << (idx % 64)) != 0
+}
+
+fn set_bit(bits: &mut [u64], idx: usize, v: bool) {
+    if v {
+        bits[idx / 64] |= 1 << (idx % 64);
+    } else {
+        bits[idx / 64] &= !(1 << (idx % 64));
+    }
+}
+
+impl Platform {
+    pub fn new(grid: &[Vec<char>]) -> Platform {
+        let nrows = grid.len();
+        let ncols = grid[0].len();
+        let words = (nrows * ncols).div_ceil(64);
+
+        let mut cubes = vec![0; words];
+        let mut rounds = vec![0; words];
+        for (row, line) in grid.iter().enumerate() {
+            for (col, &c) in line.iter().enumerate() {
+                match c {
+                    '#' => set_bit(&mut cubes, row * ncols + col, true),
+                    'O' => set_bit(&mut rounds, row * ncols + col, true),
+                    _ => (),
+                }
+            }
+        }
+
+        let mut platform = Platform {
+            nrows,
+            ncols,
+            cubes,
+            rounds,
+            runs: Default::default(),
+        };
+        for dir in SPIN_CYCLE {
+            platform.runs[dir as usize] = platform.find_runs(dir);
+        }
+        platform
+    }
+
+    /// Lanes of cells for `dir`, each starting at the edge rocks roll towards.
+    fn lanes(&self, dir: Dir) -> Vec<Vec<usize>> {
+        let (nrows, ncols) = (self.nrows, self.ncols);
+        match dir {
+            Dir::North => (0..ncols)
+                .map(|c| (0..nrows).map(|r| r * ncols + c).collect())
+                .collect(),
+            Dir::South => (0..ncols)
+                .map(|c| (0..nrows).rev().map(|r| r * ncols + c).collect())
+                .collect(),
+            Dir::West => (0..nrows)
+                .map(|r| (0..ncols).map(|c| r * ncols + c).collect())
+                .collect(),
+            Dir::East => (0..nrows)
+                .map(|r| (0..ncols).rev().map(|c| r * ncols + c).collect())
+                .collect(),
+        }
+    }
+
+    fn find_runs(&self, dir: Dir) -> Vec<Vec<usize>> {
+        let mut runs = vec![];
+        for lane in self.lanes(dir) {
+            for run in lane.split(|&idx| get_bit(&self.cubes, idx)) {
+                if !run.is_empty() {
+                    runs.push(run.to_vec());
+                }
+            }
+        }
+        runs
+    }
+
+    pub fn tilt(&mut self, dir: Dir) {
+        for run in self.runs[dir as usize].iter() {
+            let num_round = run
+                .iter()
+                .filter(|&&idx| get_bit(&self.rounds, idx))
+                .count();
+            for (n, &idx) in run.iter().enumerate() {
+                set_bit(&mut self.rounds, idx, n < num_round);
+            }
+        }
+    }
+
+    /// Tilts in each direction of `order` in turn.
+    pub fn spin(&mut self, order: &[Dir]) {
+        for &dir in order {
+            self.tilt(dir);
+        }
+    }
+
+    /// Tilts north, west, south and then east.
+    pub fn spin_cycle(&mut self) {
+        self.spin(&SPIN_CYCLE);
+    }
+
+    /// Runs `count` spins of `order`, skipping ahead once the platform
+    /// repeats a previously seen arrangement.
+    pub fn spin_cycles(&mut self, order: &[Dir], count: usize) {
+        let mut seen = HashMap::new();
+        let mut n = 0;
+        while n < count {
+            if let Some(earlier) = seen.insert(self.rounds.clone(), n) {
+                let period = n - earlier;
+                n = count - (count - n) % period;
+                seen.clear();
+                if n == count {
+                    break;
+                }
+            }
+            self.spin(order);
+            n += 1;
+        }
+    }
+
+    /// Spins a copy of the platform with `order` until it repeats, recording
+    /// the load against `edge` after every cycle.
+    pub fn detect_cycle(&self, order: &[Dir], edge: Dir) -> CycleReport {
+        let mut platform = self.clone();
+        let mut seen = HashMap::new();
+        let mut loads = vec![];
+        loop {
+            if let Some(start) = seen.insert(platform.rounds.clone(), loads.len()) {
+                let period = loads.len() - start;
+                return CycleReport {
+                    start,
+                    period,
+                    loads,
+                };
+            }
+            loads.push(platform.load(edge));
+            platform.spin(order);
+        }
+    }
+
+    /// Total load on the `edge` support beams, where each round rock counts
+    /// its distance in rows or columns from the opposite edge.
+    pub fn load(&self, edge: Dir) -> usize {
+        (0..self.nrows * self.ncols)
+            .filter(|&idx| get_bit(&self.rounds, idx))
+            .map(|idx| {
+                let (row, col) = (idx / self.ncols, idx % self.ncols);
+                match edge {
+                    Dir::North => self.nrows - row,
+                    Dir::South => row + 1,
+                    Dir::West => self.ncols - col,
+                    Dir::East => col + 1,
+                }
+            })
+            .sum()
+    }
+}
+
+impl Display for Platform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in 0..self.nrows {
+            for col in 0..self.ncols {
+                let idx = row * self.ncols + col;
+                if get_bit(&self.cubes, idx) {
+                    f.write_char('#')?
+                } else if get_bit(&self.rounds, idx) {
+                    f.write_char('O')?
+                } else {
+                    f.write_char('.')?
+                }
+            }
+            f.write_char('\n')?
+        }
+        Ok(())
+    }
+}
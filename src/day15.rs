@@ -0,0 +1,177 @@
+use std::{collections::HashMap, fmt, str::FromStr};
+
+use anyhow::{anyhow, Error};
+
+/// The Holiday ASCII String Helper algorithm.
+pub fn hash_alg(s: &str) -> u8 {
+    s.bytes()
+        .fold(0, |acc, c| acc.wrapping_add(c).wrapping_mul(17))
+}
+
+/// Splits the initialization sequence on commas, ignoring newlines.
+pub fn split_steps(s: &str) -> Vec<String> {
+    s.replace(['\n', '\r'], "")
+        .split(',')
+        .filter(|step| !step.is_empty())
+        .map(|step| step.to_string())
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Op {
+    Insert(String, u8),
+    Remove(String),
+}
+
+impl Op {
+    pub fn label(&self) -> &str {
+        match self {
+            Op::Insert(label, _) | Op::Remove(label) => label,
+        }
+    }
+}
+
+impl FromStr for Op {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (label, op) = match s.find(['=', '-']) {
+            Some(at) => (&s[..at], &s[at..]),
+            None => return Err(anyhow!("step {s:?} has no '=' or '-'")),
+        };
+        if label.is_empty() || !label.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Err(anyhow!("step {s:?} has a bad label {label:?}"));
+        }
+
+        match op.split_at(1) {
+            ("-", "") => Ok(Op::Remove(label.to_string())),
+            ("=", focal) => match focal.parse() {
+                Ok(n @ 1..=9) => Ok(Op::Insert(label.to_string(), n)),
+                _ => Err(anyhow!("step {s:?} has a bad focal length {focal:?}")),
+            },
+            _ => Err(anyhow!("step {s:?} has trailing input after '-'")),
+        }
+    }
+}
+
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Op::Insert(label, focal) => write!(f, "{label}={focal}"),
+            Op::Remove(label) => write!(f, "{label}-"),
+        }
+    }
+}
+
+/// Parses every step of the sequence, naming the first one that's wrong.
+pub fn parse_ops(s: &str) -> Result<Vec<Op>, Error> {
+    split_steps(s)
+        .iter()
+        .enumerate()
+        .map(|(n, step)| {
+            step.parse()
+                .map_err(|err: Error| err.context(format!("step {}", n + 1)))
+        })
+        .collect()
+}
+
+/// One box of lenses, front to back, with an index from label to slot so
+/// finding a lens is a lookup rather than a scan.
+#[derive(Debug, Clone, Default)]
+struct LensBox {
+    slots: Vec<(String, u8)>,
+    index: HashMap<String, usize>,
+}
+
+impl LensBox {
+    fn lenses(&self) -> impl Iterator<Item = &(String, u8)> {
+        self.slots.iter()
+    }
+}
+
+/// The 256 boxes of the lens-library HASHMAP procedure.
+#[derive(Debug, Clone)]
+pub struct LensBoxes {
+    boxes: Vec<LensBox>,
+}
+
+impl Default for LensBoxes {
+    fn default() -> Self {
+        LensBoxes {
+            boxes: vec![LensBox::default(); 256],
+        }
+    }
+}
+
+impl LensBoxes {
+    pub fn new() -> LensBoxes {
+        LensBoxes::default()
+    }
+
+    /// Puts a lens in its box, replacing one with the same label in place.
+    pub fn insert(&mut self, label: &str, focal: u8) {
+        let b = &mut self.boxes[hash_alg(label) as usize];
+        match b.index.get(label) {
+            Some(&slot) => b.slots[slot].1 = focal,
+            None => {
+                b.index.insert(label.to_string(), b.slots.len());
+                b.slots.push((label.to_string(), focal));
+            }
+        }
+    }
+
+    /// Takes the lens with `label` out of its box, if it's there, moving
+    /// the lenses behind it forward.
+    pub fn remove(&mut self, label: &str) {
+        let b = &mut self.boxes[hash_alg(label) as usize];
+        if let Some(slot) = b.index.remove(label) {
+            b.slots.remove(slot);
+            for (label, _) in b.slots[slot..].iter() {
+                *b.index.get_mut(label).unwrap() -= 1;
+            }
+        }
+    }
+
+    pub fn apply(&mut self, op: &Op) {
+        match op {
+            Op::Insert(label, focal) => self.insert(label, *focal),
+            Op::Remove(label) => self.remove(label),
+        }
+    }
+
+    /// Lenses in box `n`, front to back.
+    pub fn lenses(&self, n: u8) -> impl Iterator<Item = (&str, u8)> {
+        self.boxes[n as usize]
+            .lenses()
+            .map(|(label, focal)| (label.as_str(), *focal))
+    }
+
+    pub fn focusing_power(&self) -> usize {
+        self.boxes
+            .iter()
+            .enumerate()
+            .flat_map(|(n, b)| {
+                b.lenses()
+                    .enumerate()
+                    .map(move |(slot, &(_, focal))| (n + 1) * (slot + 1) * focal as usize)
+            })
+            .sum()
+    }
+}
+
+/// Lists the non-empty boxes like the puzzle statement does.
+impl fmt::Display for LensBoxes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (n, b) in self.boxes.iter().enumerate() {
+            if b.slots.is_empty() {
+                continue;
+            }
+            write!(f, "Box {n}:")?;
+            for (label, focal) in b.lenses() {
+                write!(f, " [{label} {focal}]")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,433 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use itertools::Itertools;
+use nom::{
+    character::{complete::*, streaming::newline},
+    multi::*,
+    sequence::*,
+    IResult,
+};
+use pathfinding::directed::{
+    strongly_connected_components::strongly_connected_components,
+    topological_sort::topological_sort,
+};
+
+pub fn parse_grid(i: &str) -> IResult<&str, Vec<Vec<char>>> {
+    let parse_line = terminated(many1(one_of(r".|-/\")), newline);
+    let (i, grid) = terminated(many1(parse_line), multispace0)(i)?;
+    Ok((i, grid))
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum Dir {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Dir {
+    pub fn arrow(&self) -> char {
+        use Dir::*;
+        match self {
+            Up => '^',
+            Down => 'v',
+            Left => '<',
+            Right => '>',
+        }
+    }
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub struct Beam {
+    pub row: i32,
+    pub col: i32,
+    pub dir: Dir,
+}
+
+impl Beam {
+    pub fn proj(&self, dir: Dir) -> Beam {
+        use Dir::*;
+        match dir {
+            Up => Beam {
+                col: self.col,
+                row: self.row - 1,
+                dir,
+            },
+            Down => Beam {
+                col: self.col,
+                row: self.row + 1,
+                dir,
+            },
+            Left => Beam {
+                col: self.col - 1,
+                row: self.row,
+                dir,
+            },
+            Right => Beam {
+                col: self.col + 1,
+                row: self.row,
+                dir,
+            },
+        }
+    }
+}
+
+/// Directions a beam leaves `tile` in after entering it travelling `dir`.
+fn deflect(tile: char, dir: Dir) -> Vec<Dir> {
+    use Dir::*;
+    match (tile, dir) {
+        ('|', Left | Right) => vec![Up, Down],
+        ('-', Up | Down) => vec![Right, Left],
+        ('\\', Up) | ('/', Down) => vec![Left],
+        ('\\', Down) | ('/', Up) => vec![Right],
+        ('\\', Left) | ('/', Right) => vec![Up],
+        ('\\', Right) | ('/', Left) => vec![Down],
+        _ => vec![dir],
+    }
+}
+
+/// A straight run of beam through empty space. `cells` are the `.` tiles it
+/// crosses and `end` is the node for the mirror or splitter it runs into, if
+/// it doesn't leave the contraption first.
+#[derive(Debug, Clone)]
+pub struct Segment {
+    pub dir: Dir,
+    pub cells: Vec<(usize, usize)>,
+    pub end: Option<usize>,
+}
+
+/// A beam entering a mirror or splitter from a given direction.
+#[derive(Debug, Clone)]
+pub struct Node {
+    pub row: usize,
+    pub col: usize,
+    pub dir: Dir,
+    pub segments: Vec<Segment>,
+}
+
+/// The contraption compressed into a graph of beam segments between mirrors
+/// and splitters, with the set of tiles energized from every node computed
+/// up front so any entry beam can be answered with a single walk.
+pub struct BeamTracer {
+    grid: Vec<Vec<char>>,
+    nodes: Vec<Node>,
+    node_ids: HashMap<(usize, usize, Dir), usize>,
+    energized: Vec<Vec<u64>>,
+}
+
+fn set_bit(bits: &mut [u64], idx: usize) {
+    bits[idx / 64] |= 1 << (idx % 64);
+}
+
+fn union_bits(bits: &mut [u64], other: &[u64]) {
+    bits.iter_mut().zip(other).for_each(|(a, b)| *a |= b);
+}
+
+impl BeamTracer {
+    pub fn new(grid: Vec<Vec<char>>) -> BeamTracer {
+        let mut tracer = BeamTracer {
+            grid,
+            nodes: vec![],
+            node_ids: HashMap::new(),
+            energized: vec![],
+        };
+
+        for (row, line) in tracer.grid.iter().enumerate() {
+            for (col, &tile) in line.iter().enumerate() {
+                if tile == '.' {
+                    continue;
+                }
+                for dir in [Dir::Up, Dir::Down, Dir::Left, Dir::Right] {
+                    tracer.node_ids.insert((row, col, dir), tracer.nodes.len());
+                    tracer.nodes.push(Node {
+                        row,
+                        col,
+                        dir,
+                        segments: vec![],
+                    });
+                }
+            }
+        }
+
+        for id in 0..tracer.nodes.len() {
+            let node = &tracer.nodes[id];
+            let at = Beam {
+                row: node.row as i32,
+                col: node.col as i32,
+                dir: node.dir,
+            };
+            let segments = deflect(tracer.grid[node.row][node.col], node.dir)
+                .into_iter()
+                .map(|dir| tracer.walk(at.proj(dir)))
+                .collect_vec();
+            tracer.nodes[id].segments = segments;
+        }
+
+        tracer.energized = tracer.close_energized();
+        tracer
+    }
+
+    pub fn nrows(&self) -> usize {
+        self.grid.len()
+    }
+
+    pub fn ncols(&self) -> usize {
+        self.grid[0].len()
+    }
+
+    pub fn grid(&self) -> &[Vec<char>] {
+        &self.grid
+    }
+
+    pub fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    fn in_bounds(&self, b: &Beam) -> bool {
+        b.row >= 0 && b.row < self.nrows() as i32 && b.col >= 0 && b.col < self.ncols() as i32
+    }
+
+    /// Follows `beam` through empty tiles until it hits a mirror or splitter
+    /// or leaves the contraption.
+    fn walk(&self, mut beam: Beam) -> Segment {
+        let mut cells = vec![];
+        while self.in_bounds(&beam) {
+            let (row, col) = (beam.row as usize, beam.col as usize);
+            if self.grid[row][col] != '.' {
+                return Segment {
+                    dir: beam.dir,
+                    cells,
+                    end: Some(self.node_ids[&(row, col, beam.dir)]),
+                };
+            }
+            cells.push((row, col));
+            beam = beam.proj(beam.dir);
+        }
+        Segment {
+            dir: beam.dir,
+            cells,
+            end: None,
+        }
+    }
+
+    /// Computes the tiles energized from each node. Nodes are grouped into
+    /// strongly connected components so beam loops share one set, and the
+    /// components are visited in reverse topological order of the condensed
+    /// graph so successors are always ready.
+    fn close_energized(&self) -> Vec<Vec<u64>> {
+        let words = (self.nrows() * self.ncols()).div_ceil(64);
+        let ids = (0..self.nodes.len()).collect_vec();
+        let successors = |&id: &usize| {
+            self.nodes[id]
+                .segments
+                .iter()
+                .filter_map(|s| s.end)
+                .collect_vec()
+        };
+
+        let components = strongly_connected_components(&ids, successors);
+        let mut component_of = vec![usize::MAX; self.nodes.len()];
+        for (cid, component) in components.iter().enumerate() {
+            component.iter().for_each(|&id| component_of[id] = cid);
+        }
+
+        let cids = (0..components.len()).collect_vec();
+        let order = topological_sort(&cids, |&cid: &usize| {
+            components[cid]
+                .iter()
+                .flat_map(successors)
+                .map(|end| component_of[end])
+                .filter(|&next| next != cid)
+                .unique()
+                .collect_vec()
+        })
+        .expect("condensed graph is acyclic");
+
+        let mut energized = vec![vec![]; self.nodes.len()];
+        for cid in order.into_iter().rev() {
+            let component = &components[cid];
+            let mut bits = vec![0u64; words];
+            for &id in component.iter() {
+                let node = &self.nodes[id];
+                set_bit(&mut bits, node.row * self.ncols() + node.col);
+                for seg in node.segments.iter() {
+                    seg.cells
+                        .iter()
+                        .for_each(|&(r, c)| set_bit(&mut bits, r * self.ncols() + c));
+                    if let Some(end) = seg.end {
+                        if component_of[end] != cid {
+                            assert_eq!(energized[end].len(), words, "successor not closed yet");
+                            union_bits(&mut bits, &energized[end]);
+                        }
+                    }
+                }
+            }
+            for &id in component.iter() {
+                energized[id] = bits.clone();
+            }
+        }
+        energized
+    }
+
+    /// Bitset of tiles, indexed `row * ncols + col`, energized by `start`.
+    pub fn energized_bits(&self, start: Beam) -> Vec<u64> {
+        let words = (self.nrows() * self.ncols()).div_ceil(64);
+        let mut bits = vec![0u64; words];
+
+        let seg = self.walk(start);
+        seg.cells
+            .iter()
+            .for_each(|&(r, c)| set_bit(&mut bits, r * self.ncols() + c));
+        if let Some(end) = seg.end {
+            union_bits(&mut bits, &self.energized[end]);
+        }
+        bits
+    }
+
+    pub fn count_energized(&self, start: Beam) -> usize {
+        self.energized_bits(start)
+            .iter()
+            .map(|w| w.count_ones() as usize)
+            .sum()
+    }
+
+    /// Every beam that can enter the contraption from one of its edges.
+    pub fn edge_beams(&self) -> Vec<Beam> {
+        let nrows = self.nrows() as i32;
+        let ncols = self.ncols() as i32;
+        let rows = (0..nrows).flat_map(|row| {
+            [
+                Beam {
+                    row,
+                    col: 0,
+                    dir: Dir::Right,
+                },
+                Beam {
+                    row,
+                    col: ncols - 1,
+                    dir: Dir::Left,
+                },
+            ]
+        });
+        let cols = (0..ncols).flat_map(|col| {
+            [
+                Beam {
+                    row: 0,
+                    col,
+                    dir: Dir::Down,
+                },
+                Beam {
+                    row: nrows - 1,
+                    col,
+                    dir: Dir::Up,
+                },
+            ]
+        });
+        rows.chain(cols).collect()
+    }
+
+    /// Every segment the beam from `start` travels along, paired with the
+    /// node it leaves from (`None` for the entry segment).
+    pub fn trace(&self, start: Beam) -> Vec<(Option<usize>, Segment)> {
+        let entry = self.walk(start);
+        let mut queue = VecDeque::from_iter(entry.end);
+        let mut seen = HashSet::<usize>::from_iter(entry.end);
+        let mut segments = vec![(None, entry)];
+
+        while let Some(id) = queue.pop_front() {
+            for seg in self.nodes[id].segments.iter() {
+                if let Some(end) = seg.end {
+                    if seen.insert(end) {
+                        queue.push_back(end);
+                    }
+                }
+                segments.push((Some(id), seg.clone()));
+            }
+        }
+        segments
+    }
+
+    /// Draws the contraption with the beam direction on every empty tile it
+    /// crosses, or the number of beams if more than one passes through.
+    pub fn render_beams(&self, start: Beam) -> String {
+        let mut dirs = vec![vec![HashSet::new(); self.ncols()]; self.nrows()];
+        for (_, seg) in self.trace(start) {
+            for &(r, c) in seg.cells.iter() {
+                dirs[r][c].insert(seg.dir);
+            }
+        }
+
+        self.grid
+            .iter()
+            .zip(dirs)
+            .map(|(line, dirs)| {
+                line.iter()
+                    .zip(dirs)
+                    .map(|(&tile, dirs)| match dirs.len() {
+                        _ if tile != '.' => tile,
+                        0 => '.',
+                        1 => dirs.into_iter().next().unwrap().arrow(),
+                        n => char::from_digit(n as u32, 10).unwrap(),
+                    })
+                    .collect::<String>()
+            })
+            .join("\n")
+    }
+
+    /// Draws energized tiles as `#` and everything else as `.`.
+    pub fn render_energized(&self, start: Beam) -> String {
+        let bits = self.energized_bits(start);
+        (0..self.nrows())
+            .map(|r| {
+                (0..self.ncols())
+                    .map(|c| {
+                        let idx = r * self.ncols() + c;
+                        if bits[idx / 64] & (1 << (idx % 64)) != 0 {
+                            '#'
+                        } else {
+                            '.'
+                        }
+                    })
+                    .collect::<String>()
+            })
+            .join("\n")
+    }
+
+    /// Exports every segment from `trace` as a JSON array.
+    pub fn segments_json(&self, start: Beam) -> String {
+        let node_json = |id: Option<usize>| match id {
+            Some(id) => {
+                let n = &self.nodes[id];
+                format!(
+                    r#"{{"row": {}, "col": {}, "dir": "{:?}", "tile": "{}"}}"#,
+                    n.row,
+                    n.col,
+                    n.dir,
+                    self.grid[n.row][n.col].escape_default()
+                )
+            }
+            None => "null".to_string(),
+        };
+
+        let segments = self
+            .trace(start)
+            .into_iter()
+            .map(|(from, seg)| {
+                let cells = seg
+                    .cells
+                    .iter()
+                    .map(|(r, c)| format!("[{r}, {c}]"))
+                    .join(", ");
+                format!(
+                    r#"  {{"from": {}, "dir": "{:?}", "cells": [{}], "to": {}}}"#,
+                    node_json(from),
+                    seg.dir,
+                    cells,
+                    node_json(seg.end)
+                )
+            })
+            .join(",\n");
+        format!("[\n{segments}\n]\n")
+    }
+}
@@ -0,0 +1,184 @@
+use itertools::Itertools;
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+};
+
+use nom::{
+    character::{complete::*, streaming::newline},
+    multi::*,
+    sequence::*,
+    IResult,
+};
+
+pub fn parse_grid(i: &str) -> IResult<&str, Vec<Vec<u8>>> {
+    let parse_line = terminated(digit1, newline);
+    let (i, grid) = terminated(many1(parse_line), multispace0)(i)?;
+    let grid = grid
+        .iter()
+        .map(|x| x.bytes().map(|b| b - b'0').collect_vec())
+        .collect_vec();
+    Ok((i, grid))
+}
+
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum Dir {
+    Up,
+    Down,
+    Right,
+    Left,
+}
+
+impl Dir {
+    fn reverse(&self) -> Dir {
+        use Dir::*;
+        match self {
+            Up => Down,
+            Down => Up,
+            Right => Left,
+            Left => Right,
+        }
+    }
+
+    fn offset(&self) -> (i32, i32) {
+        use Dir::*;
+        match self {
+            Up => (-1, 0),
+            Down => (1, 0),
+            Right => (0, 1),
+            Left => (0, -1),
+        }
+    }
+
+    pub fn arrow(&self) -> char {
+        use Dir::*;
+        match self {
+            Up => '^',
+            Down => 'v',
+            Right => '>',
+            Left => '<',
+        }
+    }
+}
+
+/// A single move of the crucible: the cell it entered and the direction it
+/// was travelling when it got there.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub row: usize,
+    pub col: usize,
+    pub dir: Dir,
+}
+
+#[derive(Debug, Clone)]
+pub struct Route {
+    pub cost: u32,
+    pub steps: Vec<Step>,
+}
+
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+struct State {
+    row: i32,
+    col: i32,
+    dir: Option<Dir>,
+    run: u32,
+}
+
+/// Finds the cheapest route from the top-left to the bottom-right cell where
+/// the crucible must move at least `min_run` and at most `max_run` blocks in
+/// a straight line before turning (or stopping at the end).
+pub fn min_route(grid: &[Vec<u8>], min_run: u32, max_run: u32) -> Option<Route> {
+    let nrows = grid.len() as i32;
+    let ncols = grid.first().map_or(0, |r| r.len()) as i32;
+
+    let start = State {
+        row: 0,
+        col: 0,
+        dir: None,
+        run: 0,
+    };
+
+    let mut queue = BinaryHeap::new();
+    let mut costs = HashMap::new();
+    let mut prev = HashMap::new();
+
+    costs.insert(start, 0);
+    queue.push(Reverse((0, start)));
+
+    while let Some(Reverse((cost, state))) = queue.pop() {
+        if costs.get(&state).is_some_and(|&c| cost > c) {
+            continue;
+        }
+
+        // check end condition
+        if state.row == nrows - 1 && state.col == ncols - 1 && state.run >= min_run {
+            let mut steps = vec![];
+            let mut cur = state;
+            while let Some(dir) = cur.dir {
+                steps.push(Step {
+                    row: cur.row as usize,
+                    col: cur.col as usize,
+                    dir,
+                });
+                cur = prev[&cur];
+            }
+            steps.reverse();
+            return Some(Route { cost, steps });
+        }
+
+        for dir in [Dir::Up, Dir::Down, Dir::Right, Dir::Left] {
+            let run = match state.dir {
+                None => 1,
+                Some(d) if d == dir => state.run + 1,
+                Some(d) if d.reverse() == dir => continue,
+                Some(_) if state.run < min_run => continue,
+                Some(_) => 1,
+            };
+            if run > max_run {
+                continue;
+            }
+
+            let (dr, dc) = dir.offset();
+            let (row, col) = (state.row + dr, state.col + dc);
+
+            // check out of bounds
+            if row < 0 || row >= nrows || col < 0 || col >= ncols {
+                continue;
+            }
+
+            let next = State {
+                row,
+                col,
+                dir: Some(dir),
+                run,
+            };
+            let next_cost = cost + grid[row as usize][col as usize] as u32;
+
+            if costs.get(&next).is_none_or(|&c| next_cost < c) {
+                costs.insert(next, next_cost);
+                prev.insert(next, state);
+                queue.push(Reverse((next_cost, next)));
+            }
+        }
+    }
+
+    None
+}
+
+/// Draws the heat-loss grid with the cells along `route` replaced by the
+/// direction the crucible was moving, as in the puzzle statement.
+pub fn render_route(grid: &[Vec<u8>], route: &Route) -> String {
+    let mut canvas = grid
+        .iter()
+        .map(|row| row.iter().map(|&v| (b'0' + v) as char).collect_vec())
+        .collect_vec();
+
+    for step in route.steps.iter() {
+        canvas[step.row][step.col] = step.dir.arrow();
+    }
+
+    canvas
+        .into_iter()
+        .map(|row| row.into_iter().collect::<String>())
+        .join("\n")
+}
@@ -0,0 +1,171 @@
+use anyhow::{anyhow, Error};
+use itertools::Itertools;
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::*,
+    combinator::{map_res, value},
+    multi::*,
+    sequence::*,
+    IResult,
+};
+
+use crate::svg::PolygonSvg;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dir {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Dir {
+    /// Unit step as `(dx, dy)`, with `y` growing downwards.
+    pub fn delta(&self) -> (i64, i64) {
+        match self {
+            Dir::Up => (0, -1),
+            Dir::Down => (0, 1),
+            Dir::Left => (-1, 0),
+            Dir::Right => (1, 0),
+        }
+    }
+}
+
+/// How to read a dig plan line: the direction and length as written, or
+/// the instruction hidden in the hex color (five hex digits of length and
+/// one for the direction).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Literal,
+    Hex,
+}
+
+/// One line of the dig plan, keeping everything on it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Instruction {
+    pub dir: Dir,
+    pub length: i64,
+    pub color: u32,
+}
+
+impl Instruction {
+    pub fn color_hex(&self) -> String {
+        format!("#{:06x}", self.color)
+    }
+
+    /// The direction and length this line digs in `mode`. In hex mode the
+    /// last color digit must be 0 to 3.
+    pub fn step(&self, mode: Mode) -> Result<(Dir, i64), Error> {
+        match mode {
+            Mode::Literal => Ok((self.dir, self.length)),
+            Mode::Hex => {
+                let dir = match self.color & 0xf {
+                    0 => Dir::Right,
+                    1 => Dir::Down,
+                    2 => Dir::Left,
+                    3 => Dir::Up,
+                    d => {
+                        return Err(anyhow!(
+                            "color {} has unknown direction digit {d:x}",
+                            self.color_hex()
+                        ))
+                    }
+                };
+                Ok((dir, (self.color >> 4) as i64))
+            }
+        }
+    }
+}
+
+fn parse_instruction(i: &str) -> IResult<&str, Instruction> {
+    let dir = alt((
+        value(Dir::Up, char('U')),
+        value(Dir::Down, char('D')),
+        value(Dir::Left, char('L')),
+        value(Dir::Right, char('R')),
+    ));
+    let color = map_res(hex_digit1, |s: &str| u32::from_str_radix(s, 16));
+    let (i, (dir, length, color)) = tuple((
+        terminated(dir, space1),
+        terminated(i64, space1),
+        delimited(tag("(#"), color, char(')')),
+    ))(i)?;
+    Ok((i, Instruction { dir, length, color }))
+}
+
+/// Parses the dig plan. Colors are kept as they are; whether they make
+/// sense as hex instructions is only checked when hex mode is used.
+pub fn parse_plan(i: &str) -> IResult<&str, Plan> {
+    let (i, instructions) =
+        terminated(separated_list1(line_ending, parse_instruction), multispace0)(i)?;
+    Ok((i, Plan { instructions }))
+}
+
+#[derive(Debug, Clone)]
+pub struct Plan {
+    pub instructions: Vec<Instruction>,
+}
+
+impl Plan {
+    /// Every line's step in `mode`, failing on the first line that can't
+    /// be read that way.
+    pub fn steps(&self, mode: Mode) -> Result<Vec<(Dir, i64)>, Error> {
+        self.instructions
+            .iter()
+            .enumerate()
+            .map(|(n, ins)| {
+                ins.step(mode)
+                    .map_err(|err| err.context(format!("line {}", n + 1)))
+            })
+            .collect()
+    }
+
+    /// Corners of the trench, starting and ending at the origin.
+    pub fn vertices(&self, mode: Mode) -> Result<Vec<(i64, i64)>, Error> {
+        let mut at = (0, 0);
+        let mut vertices = vec![at];
+        for (dir, length) in self.steps(mode)? {
+            let (dx, dy) = dir.delta();
+            at = (at.0 + dx * length, at.1 + dy * length);
+            vertices.push(at);
+        }
+        Ok(vertices)
+    }
+
+    pub fn trench_length(&self, mode: Mode) -> Result<i64, Error> {
+        Ok(self.steps(mode)?.iter().map(|&(_, length)| length).sum())
+    }
+
+    /// Cubic metres of lava the lagoon holds: the shoelace area of the
+    /// trench's centre line plus the half of each trench cube outside it.
+    pub fn lagoon_size(&self, mode: Mode) -> Result<i64, Error> {
+        let twice_area = self
+            .vertices(mode)?
+            .iter()
+            .tuple_windows()
+            .map(|(a, b)| a.0 * b.1 - b.0 * a.1)
+            .sum::<i64>()
+            .abs();
+        Ok(twice_area / 2 + self.trench_length(mode)? / 2 + 1)
+    }
+
+    /// Draws the trench outline as SVG, each segment stroked in the color
+    /// given on its line of the plan, optionally with the lagoon filled in
+    /// and the corners marked.
+    pub fn render_svg(&self, mode: Mode, fill: bool, markers: bool) -> Result<String, Error> {
+        let colors = self
+            .instructions
+            .iter()
+            .map(|ins| ins.color_hex())
+            .collect();
+        let svg = PolygonSvg::new(&self.vertices(mode)?)
+            .edge_colors(colors)
+            .markers(markers);
+        if fill {
+            Ok(svg.fill("#ccc").render())
+        } else {
+            Ok(svg.render())
+        }
+    }
+}
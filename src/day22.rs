@@ -0,0 +1,347 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Write,
+    str::FromStr,
+};
+
+use anyhow::{anyhow, Error};
+use itertools::Itertools;
+use nom::{character::complete::*, combinator::*, multi::*, sequence::*, IResult};
+
+pub type Point = (i32, i32, i32);
+
+/// A brick given by its two end cubes, which differ along at most one axis.
+/// `id` is the brick's position in the puzzle input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Brick {
+    pub id: usize,
+    pub start: Point,
+    pub end: Point,
+}
+
+impl Brick {
+    pub fn min(&self) -> Point {
+        (
+            self.start.0.min(self.end.0),
+            self.start.1.min(self.end.1),
+            self.start.2.min(self.end.2),
+        )
+    }
+
+    pub fn max(&self) -> Point {
+        (
+            self.start.0.max(self.end.0),
+            self.start.1.max(self.end.1),
+            self.start.2.max(self.end.2),
+        )
+    }
+
+    /// The `(x, y)` columns the brick covers when seen from above.
+    pub fn footprint(&self) -> impl Iterator<Item = (i32, i32)> {
+        let (min, max) = (self.min(), self.max());
+        (min.0..=max.0).cartesian_product(min.1..=max.1)
+    }
+
+    /// The brick moved down so its lowest cube sits at `z`.
+    fn dropped_to(&self, z: i32) -> Brick {
+        let dz = self.min().2 - z;
+        Brick {
+            id: self.id,
+            start: (self.start.0, self.start.1, self.start.2 - dz),
+            end: (self.end.0, self.end.1, self.end.2 - dz),
+        }
+    }
+}
+
+pub fn parse_bricks(i: &str) -> IResult<&str, Vec<Brick>> {
+    let point = || {
+        map(
+            tuple((u32, preceded(char(','), u32), preceded(char(','), u32))),
+            |(x, y, z)| (x as i32, y as i32, z as i32),
+        )
+    };
+    let brick = separated_pair(point(), char('~'), point());
+    let (i, ends) = terminated(separated_list1(line_ending, brick), multispace0)(i)?;
+
+    let bricks = ends
+        .into_iter()
+        .enumerate()
+        .map(|(id, (start, end))| Brick { id, start, end })
+        .collect();
+    Ok((i, bricks))
+}
+
+/// The horizontal axis of a side-on view of the stack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axis {
+    X,
+    Y,
+}
+
+impl FromStr for Axis {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "x" => Ok(Axis::X),
+            "y" => Ok(Axis::Y),
+            _ => Err(anyhow!("unknown axis '{s}'")),
+        }
+    }
+}
+
+/// Bricks after they have fallen as far as they can, along with which
+/// bricks each one rests on. The ground is at `z = 0`.
+///
+/// The support graph is also kept as a dominator tree rooted at the ground:
+/// a brick's immediate dominator is the lowest brick that every path down
+/// from it to the ground passes through, so removing a brick brings down
+/// exactly the bricks in its subtree.
+#[derive(Debug, Clone)]
+pub struct BrickStack {
+    bricks: Vec<Brick>,
+    order: Vec<usize>,
+    supported_by: Vec<Vec<usize>>,
+    supports: Vec<Vec<usize>>,
+    idom: Vec<Option<usize>>,
+    depth: Vec<usize>,
+}
+
+impl BrickStack {
+    /// Drops the bricks lowest first onto a height map of the tallest brick
+    /// in each column, so each one lands in a single step. Brick ids must be
+    /// their index in `bricks`.
+    pub fn settle(bricks: &[Brick]) -> BrickStack {
+        let order = bricks
+            .iter()
+            .sorted_by_key(|b| b.min().2)
+            .map(|b| b.id)
+            .collect_vec();
+
+        let mut settled = bricks.to_vec();
+        let mut supported_by = vec![vec![]; bricks.len()];
+        let mut supports = vec![vec![]; bricks.len()];
+        let mut heights: HashMap<(i32, i32), (i32, usize)> = HashMap::new();
+
+        for &id in order.iter() {
+            let brick = &bricks[id];
+            let below = brick
+                .footprint()
+                .filter_map(|xy| heights.get(&xy).copied())
+                .collect_vec();
+            let top = below.iter().map(|&(z, _)| z).max().unwrap_or(0);
+            let resting_on = below
+                .iter()
+                .filter(|&&(z, _)| z == top)
+                .map(|&(_, other)| other)
+                .unique()
+                .collect_vec();
+
+            let brick = brick.dropped_to(top + 1);
+            let height = brick.max().2;
+            brick.footprint().for_each(|xy| {
+                heights.insert(xy, (height, id));
+            });
+
+            for &other in resting_on.iter() {
+                supports[other].push(id);
+            }
+            supported_by[id] = resting_on;
+            settled[id] = brick;
+        }
+
+        let mut stack = BrickStack {
+            bricks: settled,
+            order,
+            supported_by,
+            supports,
+            idom: vec![None; bricks.len()],
+            depth: vec![0; bricks.len()],
+        };
+        stack.build_dominators();
+        stack
+    }
+
+    /// Works up the stack in order, so every brick's supports already have
+    /// their place in the tree and its dominator is their common ancestor.
+    fn build_dominators(&mut self) {
+        for &id in self.order.iter() {
+            let mut below = self.supported_by[id].iter().map(|&b| Some(b));
+            let first = below.next().flatten();
+            let idom = below.fold(first, |a, b| self.common_dominator(a, b));
+            self.idom[id] = idom;
+            self.depth[id] = idom.map_or(1, |d| self.depth[d] + 1);
+        }
+    }
+
+    /// Nearest common ancestor in the dominator tree, `None` being the ground.
+    fn common_dominator(&self, mut a: Option<usize>, mut b: Option<usize>) -> Option<usize> {
+        let depth = |n: Option<usize>| n.map_or(0, |n| self.depth[n]);
+        while a != b {
+            if depth(a) >= depth(b) {
+                a = a.and_then(|n| self.idom[n]);
+            } else {
+                b = b.and_then(|n| self.idom[n]);
+            }
+        }
+        a
+    }
+
+    pub fn len(&self) -> usize {
+        self.bricks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bricks.is_empty()
+    }
+
+    /// Settled bricks, indexed by id.
+    pub fn bricks(&self) -> &[Brick] {
+        &self.bricks
+    }
+
+    /// Brick ids from the bottom of the stack up; every brick comes after
+    /// the bricks it rests on.
+    pub fn order(&self) -> &[usize] {
+        &self.order
+    }
+
+    /// Bricks directly beneath `id`; empty if it is on the ground.
+    pub fn supported_by(&self, id: usize) -> &[usize] {
+        &self.supported_by[id]
+    }
+
+    /// Bricks resting directly on `id`.
+    pub fn supports(&self, id: usize) -> &[usize] {
+        &self.supports[id]
+    }
+
+    /// A brick can go without anything moving if every brick on it has
+    /// another brick to rest on.
+    pub fn can_disintegrate(&self, id: usize) -> bool {
+        self.supports[id]
+            .iter()
+            .all(|&above| self.supported_by[above].len() > 1)
+    }
+
+    /// The lowest brick `id` can't reach the ground without, or `None` if
+    /// it has more than one way down.
+    pub fn immediate_dominator(&self, id: usize) -> Option<usize> {
+        self.idom[id]
+    }
+
+    /// How many other bricks fall if each brick is removed, indexed by id.
+    /// This is the size of the brick's dominator subtree less itself.
+    pub fn fall_counts(&self) -> Vec<usize> {
+        let mut sizes = vec![1; self.len()];
+        for &id in self.order.iter().rev() {
+            if let Some(d) = self.idom[id] {
+                sizes[d] += sizes[id];
+            }
+        }
+        sizes.into_iter().map(|n| n - 1).collect()
+    }
+
+    /// Bricks that fall when all of `removed` are taken out at once, in
+    /// order up the stack.
+    pub fn falls_if_removed(&self, removed: &[usize]) -> Vec<usize> {
+        let mut gone = removed.iter().copied().collect::<HashSet<_>>();
+        let mut fallen = vec![];
+        for &id in self.order.iter() {
+            let below = &self.supported_by[id];
+            if !gone.contains(&id) && !below.is_empty() && below.iter().all(|b| gone.contains(b)) {
+                gone.insert(id);
+                fallen.push(id);
+            }
+        }
+        fallen
+    }
+
+    /// Exports the settled bricks as Wavefront OBJ, one `o brick_<id>` box
+    /// per brick with each cube spanning one unit.
+    pub fn to_obj(&self) -> String {
+        const FACES: [[usize; 4]; 6] = [
+            [1, 2, 4, 3],
+            [5, 7, 8, 6],
+            [1, 5, 6, 2],
+            [3, 4, 8, 7],
+            [1, 3, 7, 5],
+            [2, 6, 8, 4],
+        ];
+
+        let mut obj = String::new();
+        for (n, brick) in self.bricks.iter().enumerate() {
+            let (lo, hi) = (brick.min(), brick.max());
+            let (hi_x, hi_y, hi_z) = (hi.0 + 1, hi.1 + 1, hi.2 + 1);
+            writeln!(obj, "o brick_{}", brick.id).unwrap();
+            for x in [lo.0, hi_x] {
+                for y in [lo.1, hi_y] {
+                    for z in [lo.2, hi_z] {
+                        writeln!(obj, "v {x} {y} {z}").unwrap();
+                    }
+                }
+            }
+            for face in FACES {
+                let face = face.iter().map(|v| n * 8 + v).join(" ");
+                writeln!(obj, "f {face}").unwrap();
+            }
+        }
+        obj
+    }
+
+    /// Draws the stack side on, looking along the other horizontal axis,
+    /// in the style of the puzzle statement. Bricks are lettered by id when
+    /// there are few enough of them and shown as `#` otherwise; `?` marks
+    /// a cell where more than one brick lines up.
+    pub fn projection(&self, axis: Axis) -> String {
+        const LABELS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+        let label = |id: usize| match LABELS.get(id) {
+            Some(&c) if self.len() <= LABELS.len() => c as char,
+            _ => '#',
+        };
+        let across = |p: Point| match axis {
+            Axis::X => p.0,
+            Axis::Y => p.1,
+        };
+
+        let width = self
+            .bricks
+            .iter()
+            .map(|b| across(b.max()) + 1)
+            .max()
+            .unwrap_or(0);
+        let height = self.bricks.iter().map(|b| b.max().2).max().unwrap_or(0);
+
+        let mut cells: HashMap<(i32, i32), HashSet<usize>> = HashMap::new();
+        for brick in self.bricks.iter() {
+            let (lo, hi) = (brick.min(), brick.max());
+            for a in across(lo)..=across(hi) {
+                for z in lo.2..=hi.2 {
+                    cells.entry((a, z)).or_default().insert(brick.id);
+                }
+            }
+        }
+
+        let name = match axis {
+            Axis::X => 'x',
+            Axis::Y => 'y',
+        };
+        let mut out = String::new();
+        writeln!(out, "{:>w$}", name, w = width as usize / 2 + 1).unwrap();
+        let digits = (0..width).map(|a| char::from_digit((a % 10) as u32, 10).unwrap());
+        writeln!(out, "{}", digits.collect::<String>()).unwrap();
+        for z in (1..=height).rev() {
+            let row = (0..width)
+                .map(|a| match cells.get(&(a, z)) {
+                    None => '.',
+                    Some(ids) if ids.len() == 1 => label(*ids.iter().next().unwrap()),
+                    Some(_) => '?',
+                })
+                .collect::<String>();
+            let tag = if z == (height + 1) / 2 { " z" } else { "" };
+            writeln!(out, "{row} {z}{tag}").unwrap();
+        }
+        writeln!(out, "{} 0", "-".repeat(width as usize)).unwrap();
+        out
+    }
+}
@@ -0,0 +1,148 @@
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::{anyhow, Error};
+
+/// A number in the schematic covering columns `start..end` of `row`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub value: u64,
+    pub row: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Any character other than a digit or `.`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Symbol {
+    pub kind: char,
+    pub row: usize,
+    pub col: usize,
+}
+
+/// Numbers and symbols pulled out of the engine schematic, with which
+/// symbols touch each number (diagonals included) indexed both ways.
+#[derive(Debug, Clone)]
+pub struct Schematic {
+    numbers: Vec<Span>,
+    symbols: Vec<Symbol>,
+    number_symbols: Vec<Vec<usize>>,
+    symbol_numbers: Vec<Vec<usize>>,
+}
+
+impl Schematic {
+    pub fn new<S: AsRef<str>>(lines: &[S]) -> Result<Schematic, Error> {
+        let mut numbers = vec![];
+        let mut symbols = vec![];
+
+        for (row, line) in lines.iter().enumerate() {
+            let line = line.as_ref();
+            // columns count chars, so anything non-ASCII still lines up;
+            // `digits` keeps the byte offset alongside for slicing
+            let width = line.chars().count();
+            let mut digits: Option<(usize, usize)> = None;
+            let chars = line.char_indices().enumerate();
+            for (col, (byte, c)) in chars.chain([(width, (line.len(), '.'))]) {
+                match (c.is_ascii_digit(), digits) {
+                    (true, None) => digits = Some((col, byte)),
+                    (false, Some((start, from))) => {
+                        let text = &line[from..byte];
+                        let value = text.parse().map_err(|err| {
+                            anyhow!(
+                                "row {}, col {}: bad number {text:?}: {err}",
+                                row + 1,
+                                start + 1
+                            )
+                        })?;
+                        numbers.push(Span {
+                            value,
+                            row,
+                            start,
+                            end: col,
+                        });
+                        digits = None;
+                    }
+                    _ => (),
+                }
+                if !c.is_ascii_digit() && c != '.' && col < width {
+                    symbols.push(Symbol { kind: c, row, col });
+                }
+            }
+        }
+
+        let at: HashMap<(usize, usize), usize> = symbols
+            .iter()
+            .enumerate()
+            .map(|(n, s)| ((s.row, s.col), n))
+            .collect();
+
+        let mut number_symbols = vec![vec![]; numbers.len()];
+        let mut symbol_numbers = vec![vec![]; symbols.len()];
+        for (n, span) in numbers.iter().enumerate() {
+            for row in span.row.saturating_sub(1)..=span.row + 1 {
+                for col in span.start.saturating_sub(1)..=span.end {
+                    if let Some(&s) = at.get(&(row, col)) {
+                        number_symbols[n].push(s);
+                        symbol_numbers[s].push(n);
+                    }
+                }
+            }
+        }
+
+        Ok(Schematic {
+            numbers,
+            symbols,
+            number_symbols,
+            symbol_numbers,
+        })
+    }
+
+    pub fn numbers(&self) -> &[Span] {
+        &self.numbers
+    }
+
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+
+    /// Symbols touching the `n`th number.
+    pub fn symbols_near(&self, n: usize) -> impl Iterator<Item = &Symbol> {
+        self.number_symbols[n].iter().map(|&s| &self.symbols[s])
+    }
+
+    /// Numbers touching the `s`th symbol.
+    pub fn numbers_near(&self, s: usize) -> impl Iterator<Item = &Span> {
+        self.symbol_numbers[s].iter().map(|&n| &self.numbers[n])
+    }
+
+    /// Numbers adjacent to at least one symbol.
+    pub fn part_numbers(&self) -> impl Iterator<Item = &Span> {
+        self.numbers
+            .iter()
+            .zip(self.number_symbols.iter())
+            .filter(|(_, syms)| !syms.is_empty())
+            .map(|(span, _)| span)
+    }
+
+    /// Indices of symbols with exactly `count` adjacent numbers.
+    pub fn symbols_with(&self, count: usize) -> impl Iterator<Item = usize> + '_ {
+        (0..self.symbols.len()).filter(move |&s| self.symbol_numbers[s].len() == count)
+    }
+
+    /// For every symbol kind, the sum over symbols of that kind with exactly
+    /// `count` adjacent numbers of the product of those numbers. Fails if a
+    /// product or sum doesn't fit in a `u64`.
+    pub fn product_sums(&self, count: usize) -> Result<BTreeMap<char, u64>, Error> {
+        let mut sums = BTreeMap::new();
+        for s in self.symbols_with(count) {
+            let Symbol { kind, row, col } = self.symbols[s];
+            let overflow = || anyhow!("row {}, col {}: {kind} overflows u64", row + 1, col + 1);
+            let product = self
+                .numbers_near(s)
+                .try_fold(1u64, |acc, n| acc.checked_mul(n.value))
+                .ok_or_else(overflow)?;
+            let sum: &mut u64 = sums.entry(kind).or_default();
+            *sum = sum.checked_add(product).ok_or_else(overflow)?;
+        }
+        Ok(sums)
+    }
+}
@@ -0,0 +1,131 @@
+use std::{collections::HashSet, ops::Range, str::FromStr};
+
+use anyhow::{anyhow, Error};
+
+#[derive(Debug, Clone)]
+pub struct Card {
+    pub id: u32,
+    pub wins: HashSet<u32>,
+    pub guesses: Vec<u32>,
+}
+
+fn parse_numbers(s: &str) -> Result<Vec<u32>, Error> {
+    s.split_whitespace()
+        .map(|n| n.parse().map_err(|_| anyhow!("bad number {n:?}")))
+        .collect()
+}
+
+impl FromStr for Card {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (label, numbers) = s
+            .split_once(':')
+            .ok_or(anyhow!("missing ':' after card label in {s:?}"))?;
+        let id = label
+            .trim()
+            .strip_prefix("Card")
+            .ok_or(anyhow!("expected 'Card <id>' in {s:?}"))?
+            .trim()
+            .parse()
+            .map_err(|_| anyhow!("bad card id in {s:?}"))?;
+        let (wins, guesses) = numbers
+            .split_once('|')
+            .ok_or(anyhow!("missing '|' separator in {s:?}"))?;
+
+        Ok(Card {
+            id,
+            wins: parse_numbers(wins)?.into_iter().collect(),
+            guesses: parse_numbers(guesses)?,
+        })
+    }
+}
+
+impl Card {
+    pub fn matches(&self) -> usize {
+        self.guesses
+            .iter()
+            .filter(|g| self.wins.contains(g))
+            .count()
+    }
+
+    /// One point for the first match, doubled for each match after it.
+    pub fn points(&self) -> Result<u64, Error> {
+        match self.matches() {
+            0 => Ok(0),
+            n => u32::try_from(n - 1)
+                .ok()
+                .and_then(|e| 2u64.checked_pow(e))
+                .ok_or(anyhow!("card {}: {n} matches is too many points", self.id)),
+        }
+    }
+}
+
+/// Which cards a single copy of a card wins, as indices into the pile.
+pub trait CopyRule {
+    fn won(&self, index: usize, card: &Card) -> Range<usize>;
+}
+
+/// The puzzle's rule: a card with `n` matches wins the next `n` cards.
+pub struct NextCards;
+
+impl CopyRule for NextCards {
+    fn won(&self, index: usize, card: &Card) -> Range<usize> {
+        index + 1..index + 1 + card.matches()
+    }
+}
+
+impl<F: Fn(usize, &Card) -> Range<usize>> CopyRule for F {
+    fn won(&self, index: usize, card: &Card) -> Range<usize> {
+        self(index, card)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Cascade {
+    pub matches: Vec<usize>,
+    pub copies: Vec<u64>,
+}
+
+impl Cascade {
+    pub fn total(&self) -> Result<u64, Error> {
+        self.copies
+            .iter()
+            .try_fold(0u64, |acc, &n| acc.checked_add(n))
+            .ok_or(anyhow!("total number of cards overflows u64"))
+    }
+}
+
+/// Counts how many copies of each card end up in the pile. Cards can only
+/// win cards after themselves, so a single pass with a running difference
+/// array settles every count; won ranges are clipped to the pile and to the
+/// cards after the winner. Copies that win their way past `u64::MAX` are an
+/// error rather than a wrapped count.
+pub fn cascade(cards: &[Card], rule: &impl CopyRule) -> Result<Cascade, Error> {
+    let n = cards.len();
+    let mut starts = vec![0u64; n + 1];
+    let mut ends = vec![0u64; n + 1];
+    let mut matches = Vec::with_capacity(n);
+    let mut copies = Vec::with_capacity(n);
+    let mut extra = 0u64;
+
+    let overflow = |card: &Card| anyhow!("card {}: number of copies overflows u64", card.id);
+    for (i, card) in cards.iter().enumerate() {
+        // every range ending here started earlier, so this can't underflow
+        extra = extra.checked_add(starts[i]).ok_or_else(|| overflow(card))? - ends[i];
+        let count = extra.checked_add(1).ok_or_else(|| overflow(card))?;
+        matches.push(card.matches());
+        copies.push(count);
+
+        let won = rule.won(i, card);
+        let (start, end) = (won.start.max(i + 1).min(n), won.end.min(n));
+        if start < end {
+            starts[start] = starts[start]
+                .checked_add(count)
+                .ok_or_else(|| overflow(card))?;
+            ends[end] = ends[end].checked_add(count).ok_or_else(|| overflow(card))?;
+        }
+    }
+
+    Ok(Cascade { matches, copies })
+}
@@ -0,0 +1,160 @@
+use std::{ops::Range, str::FromStr};
+
+use anyhow::{anyhow, Error};
+
+use crate::interval_map::IntervalMap;
+use crate::sections::Sections;
+
+#[derive(Debug)]
+pub struct Span {
+    pub dst: u64,
+    pub src: u64,
+    pub len: u64,
+}
+
+impl FromStr for Span {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut split = s.split_whitespace();
+        let mut next = || {
+            split
+                .next()
+                .ok_or(anyhow!("expected three numbers in {s:?}"))
+        };
+        let dst = next()?.parse()?;
+        let src = next()?.parse()?;
+        let len = next()?.parse()?;
+        Ok(Span { dst, src, len })
+    }
+}
+
+pub const STAGES: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+
+/// A seed followed by the value it maps to after each stage, ending at its
+/// location.
+#[derive(Debug, Clone)]
+pub struct Trace {
+    pub seed_range: Range<u64>,
+    pub values: Vec<u64>,
+}
+
+impl Trace {
+    pub fn seed(&self) -> u64 {
+        self.values[0]
+    }
+
+    pub fn location(&self) -> u64 {
+        self.values[self.values.len() - 1]
+    }
+}
+
+#[derive(Debug)]
+pub struct Almanac {
+    pub seeds: Vec<u64>,
+    pub stages: Vec<IntervalMap<u64>>,
+}
+
+impl FromStr for Almanac {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let sections = Sections::new(s);
+
+        let seeds = sections.get(0)?.body;
+        let seeds = seeds
+            .strip_prefix("seeds:")
+            .ok_or(anyhow!("expected seeds in first section"))?
+            .split_whitespace()
+            .map(|s| s.parse())
+            .collect::<Result<Vec<u64>, _>>()?;
+
+        let stages = STAGES
+            .iter()
+            .map(|stage| {
+                let section = sections.named(&format!("{stage} map"))?;
+                let mut map = IntervalMap::new();
+                for (n, span) in section.parse_lines::<Span>()?.into_iter().enumerate() {
+                    map.insert(span.src, span.len, span.dst)
+                        .map_err(|err| anyhow!("{}, line {}: {err}", section.label(), n + 1))?;
+                }
+                Ok(map)
+            })
+            .collect::<Result<_, Error>>()?;
+
+        Ok(Almanac { seeds, stages })
+    }
+}
+
+impl Almanac {
+    /// All seven stages folded into a single seed-to-location map.
+    pub fn location_map(&self) -> IntervalMap<u64> {
+        self.stages
+            .iter()
+            .fold(IntervalMap::new(), |map, stage| map.compose(stage))
+    }
+
+    /// The seed line read as `start length` pairs.
+    pub fn seed_ranges(&self) -> Result<Vec<Range<u64>>, Error> {
+        if !self.seeds.len().is_multiple_of(2) {
+            return Err(anyhow!(
+                "seeds: expected start and length pairs, got {} numbers",
+                self.seeds.len()
+            ));
+        }
+        self.seeds
+            .chunks(2)
+            .map(|v| {
+                let end = v[0].checked_add(v[1]).ok_or(anyhow!(
+                    "seeds: range {} + {} is out of range",
+                    v[0],
+                    v[1]
+                ))?;
+                Ok(v[0]..end)
+            })
+            .collect()
+    }
+
+    /// Values that `stage` sends into `ranges`, traced back through every
+    /// earlier stage to the seeds that produce them.
+    pub fn seeds_for(&self, stage: usize, ranges: &[Range<u64>]) -> Vec<Range<u64>> {
+        self.stages[..=stage]
+            .iter()
+            .rev()
+            .fold(ranges.to_vec(), |ranges, map| map.preimage_ranges(&ranges))
+    }
+
+    /// The lowest location reachable from `seeds`, along with the seed range
+    /// and seed that reach it and the value after each stage.
+    pub fn min_location(&self, seeds: &[Range<u64>]) -> Option<Trace> {
+        let location_map = self.location_map();
+        let location = location_map.map_ranges(seeds).first()?.start;
+
+        let seed = location_map
+            .preimage(location..location + 1)
+            .into_iter()
+            .flat_map(|r| {
+                seeds.iter().filter_map(move |s| {
+                    let lo = r.start.max(s.start);
+                    (lo < r.end.min(s.end)).then_some(lo)
+                })
+            })
+            .min()?;
+        let seed_range = seeds.iter().find(|s| s.contains(&seed))?.clone();
+
+        let values = self.stages.iter().fold(vec![seed], |mut values, map| {
+            values.push(map.get(values[values.len() - 1]));
+            values
+        });
+
+        Some(Trace { seed_range, values })
+    }
+}
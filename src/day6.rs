@@ -0,0 +1,94 @@
+use std::ops::RangeInclusive;
+
+use anyhow::{anyhow, Error};
+use itertools::Itertools;
+use num::{BigInt, One, Zero};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Race {
+    pub time: BigInt,
+    pub distance: BigInt,
+}
+
+impl Race {
+    fn wins(&self, hold: &BigInt) -> bool {
+        hold * (&self.time - hold) > self.distance
+    }
+
+    /// The hold times that beat the record. `h·(T−h)` peaks at `T/2`, so
+    /// if that doesn't win nothing does. Otherwise the winning holds run
+    /// from just above the smaller root of `h·(T−h) = D` to its mirror image
+    /// `T − lo`. With `s = isqrt(T² − 4D)`, `(T − s) / 2` rounded down is at
+    /// most one below the first winning hold, so it takes at most two
+    /// checks to land on it.
+    pub fn winning_holds(&self) -> Option<RangeInclusive<BigInt>> {
+        let two = BigInt::from(2);
+        if !self.wins(&(&self.time / &two)) {
+            return None;
+        }
+        let disc = &self.time * &self.time - BigInt::from(4) * &self.distance;
+        let root = disc.sqrt();
+
+        let mut lo = ((&self.time - &root) / &two).max(BigInt::zero());
+        for _ in 0..2 {
+            if !self.wins(&lo) {
+                lo += 1;
+            }
+        }
+        debug_assert!(self.wins(&lo));
+        debug_assert!(lo.is_zero() || !self.wins(&(&lo - 1)));
+
+        let hi = &self.time - &lo;
+        Some(lo..=hi)
+    }
+
+    pub fn ways_to_win(&self) -> BigInt {
+        self.winning_holds()
+            .map_or(BigInt::zero(), |r| r.end() - r.start() + BigInt::one())
+    }
+}
+
+fn parse_line<'a>(line: Option<&'a str>, label: &str) -> Result<&'a str, Error> {
+    line.and_then(|l| l.trim().strip_prefix(label))
+        .ok_or(anyhow!("expected a line starting with {label:?}"))
+}
+
+/// Reads the time and distance lines as a list of separate races.
+pub fn parse_races(s: &str) -> Result<Vec<Race>, Error> {
+    let mut lines = s.lines();
+    let times = parse_line(lines.next(), "Time:")?;
+    let distances = parse_line(lines.next(), "Distance:")?;
+
+    let times = times.split_whitespace().map(|t| t.parse()).collect_vec();
+    let distances = distances
+        .split_whitespace()
+        .map(|d| d.parse())
+        .collect_vec();
+    if times.len() != distances.len() {
+        return Err(anyhow!("different number of times and distances"));
+    }
+
+    times
+        .into_iter()
+        .zip(distances)
+        .map(|(time, distance)| {
+            Ok(Race {
+                time: time?,
+                distance: distance?,
+            })
+        })
+        .collect()
+}
+
+/// Reads the time and distance lines as one race, ignoring the spaces
+/// between digits.
+pub fn parse_kerned_race(s: &str) -> Result<Race, Error> {
+    let mut lines = s.lines();
+    let time = parse_line(lines.next(), "Time:")?;
+    let distance = parse_line(lines.next(), "Distance:")?;
+
+    Ok(Race {
+        time: time.split_whitespace().collect::<String>().parse()?,
+        distance: distance.split_whitespace().collect::<String>().parse()?,
+    })
+}
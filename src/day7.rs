@@ -0,0 +1,311 @@
+use std::{cmp::Ordering, fmt::Display, fs, io, path::PathBuf};
+
+use clap::Parser;
+
+use itertools::Itertools;
+use nom::{
+    character::complete::*,
+    multi::{count, many1},
+    sequence::terminated,
+    IResult,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HandType {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
+
+/// How two hands of the same type are ordered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TieBreak {
+    /// Compare card by card in the order they were dealt.
+    DealtOrder,
+    /// Compare card by card after sorting each hand strongest first.
+    Strongest,
+}
+
+#[derive(Debug, Clone)]
+pub struct RuleSet {
+    /// Card labels from weakest to strongest.
+    pub order: Vec<char>,
+    /// Cards that stand in for whatever makes the hand strongest.
+    pub wild: Vec<char>,
+    pub tie_break: TieBreak,
+}
+
+impl HandType {
+    pub const ALL: [HandType; 7] = [
+        HandType::HighCard,
+        HandType::OnePair,
+        HandType::TwoPair,
+        HandType::ThreeOfAKind,
+        HandType::FullHouse,
+        HandType::FourOfAKind,
+        HandType::FiveOfAKind,
+    ];
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hand {
+    pub cards: Vec<char>,
+    pub bid: u64,
+}
+
+impl Display for Hand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.cards.iter().collect::<String>())
+    }
+}
+
+impl RuleSet {
+    pub fn standard() -> RuleSet {
+        RuleSet {
+            order: "23456789TJQKA".chars().collect(),
+            wild: vec![],
+            tie_break: TieBreak::DealtOrder,
+        }
+    }
+
+    /// Jacks become jokers: wild, but the weakest card on ties.
+    pub fn jokers() -> RuleSet {
+        RuleSet {
+            order: "J23456789TQKA".chars().collect(),
+            wild: vec!['J'],
+            tie_break: TieBreak::DealtOrder,
+        }
+    }
+
+    pub fn rank(&self, card: char) -> Option<usize> {
+        self.order.iter().position(|&c| c == card)
+    }
+
+    pub fn is_wild(&self, card: char) -> bool {
+        self.wild.contains(&card)
+    }
+
+    /// The best type the hand can make. Wild cards always do best joining
+    /// the largest group of natural cards.
+    pub fn classify(&self, cards: &[char]) -> HandType {
+        let num_wild = cards.iter().filter(|&&c| self.is_wild(c)).count();
+        let mut groups = cards
+            .iter()
+            .filter(|&&c| !self.is_wild(c))
+            .counts()
+            .into_values()
+            .sorted()
+            .rev()
+            .collect_vec();
+        match groups.first_mut() {
+            Some(largest) => *largest += num_wild,
+            None => groups.push(num_wild),
+        }
+
+        match groups[..] {
+            [5, ..] => HandType::FiveOfAKind,
+            [4, ..] => HandType::FourOfAKind,
+            [3, 2, ..] => HandType::FullHouse,
+            [3, ..] => HandType::ThreeOfAKind,
+            [2, 2, ..] => HandType::TwoPair,
+            [2, ..] => HandType::OnePair,
+            _ => HandType::HighCard,
+        }
+    }
+
+    /// The card the wild cards in `cards` act as when classified: the most
+    /// common natural card, preferring the stronger one on a tie, or the
+    /// strongest natural card if every card is wild. `None` if there are no
+    /// wild cards.
+    pub fn substitution(&self, cards: &[char]) -> Option<char> {
+        if !cards.iter().any(|&c| self.is_wild(c)) {
+            return None;
+        }
+        cards
+            .iter()
+            .filter(|&&c| !self.is_wild(c))
+            .counts()
+            .into_iter()
+            .max_by_key(|&(&c, n)| (n, self.rank(c)))
+            .map(|(&c, _)| c)
+            .or_else(|| {
+                self.order
+                    .iter()
+                    .rev()
+                    .find(|&&c| !self.is_wild(c))
+                    .copied()
+            })
+    }
+
+    fn tie_break_ranks(&self, cards: &[char]) -> Vec<Option<usize>> {
+        let ranks = cards.iter().map(|&c| self.rank(c));
+        match self.tie_break {
+            TieBreak::DealtOrder => ranks.collect(),
+            TieBreak::Strongest => ranks.sorted().rev().collect(),
+        }
+    }
+
+    pub fn compare(&self, a: &Hand, b: &Hand) -> Ordering {
+        self.classify(&a.cards)
+            .cmp(&self.classify(&b.cards))
+            .then_with(|| {
+                self.tie_break_ranks(&a.cards)
+                    .cmp(&self.tie_break_ranks(&b.cards))
+            })
+    }
+
+    /// Sorts `hands` weakest first, so a hand's rank is its index plus one.
+    pub fn sort(&self, hands: &mut [Hand]) {
+        hands.sort_by(|a, b| self.compare(a, b));
+    }
+
+    pub fn total_winnings(&self, hands: &mut [Hand]) -> u64 {
+        self.sort(hands);
+        hands
+            .iter()
+            .enumerate()
+            .map(|(i, h)| (i as u64 + 1) * h.bid)
+            .sum()
+    }
+
+    /// Classifies and ranks every hand, weakest first.
+    pub fn analyze(&self, hands: &[Hand]) -> Vec<HandReport> {
+        let mut hands = hands.to_vec();
+        self.sort(&mut hands);
+        hands
+            .into_iter()
+            .enumerate()
+            .map(|(i, hand)| HandReport {
+                hand_type: self.classify(&hand.cards),
+                substitution: self.substitution(&hand.cards),
+                rank: i + 1,
+                winnings: (i as u64 + 1) * hand.bid,
+                hand,
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HandReport {
+    pub hand: Hand,
+    pub hand_type: HandType,
+    pub substitution: Option<char>,
+    pub rank: usize,
+    pub winnings: u64,
+}
+
+/// One line of the `--analyze` listing: rank, hand, type, any joker
+/// substitution, and bid times rank.
+impl Display for HandReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let substitution = self
+            .substitution
+            .map_or("-".to_string(), |c| format!("as {c}"));
+        write!(
+            f,
+            "{:>5} {} {:<12} {:>4} x {:>4} = {}",
+            self.rank,
+            self.hand,
+            format!("{:?}", self.hand_type),
+            substitution,
+            self.hand.bid,
+            self.winnings
+        )
+    }
+}
+
+/// Number of hands of each type, including types with no hands.
+pub fn histogram(reports: &[HandReport]) -> Vec<(HandType, usize)> {
+    let counts = reports.iter().map(|r| r.hand_type).counts();
+    HandType::ALL
+        .iter()
+        .map(|t| (*t, counts.get(t).copied().unwrap_or(0)))
+        .collect()
+}
+
+pub fn reports_csv(reports: &[HandReport]) -> String {
+    let mut csv = String::from("hand,bid,type,substitution,rank,winnings\n");
+    for r in reports {
+        csv += &format!(
+            "{},{},{:?},{},{},{}\n",
+            r.hand,
+            r.hand.bid,
+            r.hand_type,
+            r.substitution.map(String::from).unwrap_or_default(),
+            r.rank,
+            r.winnings
+        );
+    }
+    csv
+}
+
+/// The histogram as aligned `type count` lines.
+pub fn histogram_table(histogram: &[(HandType, usize)]) -> String {
+    let mut table = String::new();
+    for (t, n) in histogram {
+        table += &format!("{:<12} {n}\n", format!("{t:?}"));
+    }
+    table
+}
+
+pub fn histogram_csv(histogram: &[(HandType, usize)]) -> String {
+    let mut csv = String::from("type,count\n");
+    for (t, n) in histogram {
+        csv += &format!("{t:?},{n}\n");
+    }
+    csv
+}
+
+/// Output options shared by both parts.
+#[derive(Parser, Debug)]
+pub struct ReportOpts {
+    /// print each hand's type, substitution, rank and winnings
+    #[arg(short, long)]
+    pub analyze: bool,
+
+    /// write the per-hand analysis as CSV
+    #[arg(long)]
+    pub csv: Option<PathBuf>,
+
+    /// write the hand type histogram as CSV
+    #[arg(long)]
+    pub histogram_csv: Option<PathBuf>,
+}
+
+impl ReportOpts {
+    /// Prints or writes whichever reports were asked for.
+    pub fn emit(&self, reports: &[HandReport]) -> io::Result<()> {
+        let histogram = histogram(reports);
+        if self.analyze {
+            for r in reports {
+                println!("{r}");
+            }
+            print!("{}", histogram_table(&histogram));
+        }
+        if let Some(path) = &self.csv {
+            fs::write(path, reports_csv(reports))?;
+        }
+        if let Some(path) = &self.histogram_csv {
+            fs::write(path, histogram_csv(&histogram))?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_hand<'a>(i: &'a str, rules: &RuleSet) -> IResult<&'a str, Hand> {
+    let labels = rules.order.iter().collect::<String>();
+    let (i, cards) = count(one_of(labels.as_str()), 5)(i)?;
+    let (i, _) = space1(i)?;
+    let (i, bid) = u64(i)?;
+
+    Ok((i, Hand { cards, bid }))
+}
+
+pub fn parse_hands<'a>(i: &'a str, rules: &RuleSet) -> IResult<&'a str, Vec<Hand>> {
+    many1(terminated(|i| parse_hand(i, rules), multispace0))(i)
+}
@@ -0,0 +1,102 @@
+use std::str::FromStr;
+
+use anyhow::{anyhow, Error};
+use num::{BigInt, One, Zero};
+
+/// One row of the report. Rows may be empty.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sequence {
+    pub values: Vec<BigInt>,
+}
+
+impl FromStr for Sequence {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let values = s
+            .split_whitespace()
+            .map(|n| n.parse().map_err(|_| anyhow!("bad number {n:?}")))
+            .collect::<Result<_, Error>>()?;
+        Ok(Sequence { values })
+    }
+}
+
+/// The polynomial through a sequence in Newton forward-difference form,
+/// `f(x) = Σ Δᵏf(0)·C(x, k)`, so it can be evaluated at any index,
+/// including ones before the start.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Newton {
+    diffs: Vec<BigInt>,
+    len: usize,
+    confirmed: bool,
+}
+
+impl Newton {
+    /// Builds the difference table for `values`. Returns `None` if there is
+    /// nothing to fit.
+    pub fn fit(values: &[BigInt]) -> Option<Newton> {
+        if values.is_empty() {
+            return None;
+        }
+
+        let mut diffs = vec![];
+        let mut row = values.to_vec();
+        let mut confirmed = false;
+        while !row.is_empty() {
+            if row.iter().all(Zero::is_zero) {
+                confirmed = true;
+                break;
+            }
+            diffs.push(row[0].clone());
+            row = row.windows(2).map(|w| &w[1] - &w[0]).collect();
+        }
+
+        // Strip trailing zero leading-differences so the degree is exact.
+        while diffs.last().is_some_and(Zero::is_zero) {
+            diffs.pop();
+        }
+
+        Some(Newton {
+            diffs,
+            len: values.len(),
+            confirmed,
+        })
+    }
+
+    /// Degree of the fitted polynomial; the all-zero sequence counts as 0.
+    pub fn degree(&self) -> usize {
+        self.diffs.len().saturating_sub(1)
+    }
+
+    /// Whether the data reached a row of zero differences, meaning the
+    /// degree was observed rather than forced by running out of values.
+    pub fn confirmed(&self) -> bool {
+        self.confirmed
+    }
+
+    /// Value at index `x`, where the sequence occupies `0..len`. Binomials
+    /// `C(x, k)` are built up term by term, each division being exact.
+    pub fn at(&self, x: &BigInt) -> BigInt {
+        let mut binom = BigInt::one();
+        let mut total = BigInt::zero();
+        for (k, d) in self.diffs.iter().enumerate() {
+            if k > 0 {
+                binom = binom * (x - (k - 1)) / k;
+            }
+            total += d * &binom;
+        }
+        total
+    }
+
+    /// The next `n` values after the end of the sequence.
+    pub fn forward(&self, n: usize) -> Vec<BigInt> {
+        (self.len..self.len + n)
+            .map(|x| self.at(&BigInt::from(x)))
+            .collect()
+    }
+
+    /// The `n` values before the start, nearest first.
+    pub fn backward(&self, n: usize) -> Vec<BigInt> {
+        (1..=n).map(|x| self.at(&-BigInt::from(x))).collect()
+    }
+}
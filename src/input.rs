@@ -0,0 +1,77 @@
+use std::fs::File;
+use std::io::{self, prelude::*, BufReader};
+use std::path::Path;
+
+/// Reads input a line at a time without holding the whole file in memory.
+/// Lines are handed out borrowed from an internal buffer with any `\n` or
+/// `\r\n` terminator removed, and a missing newline on the final line is
+/// fine.
+pub struct LineReader<R> {
+    reader: R,
+    line: String,
+    block: String,
+}
+
+pub(crate) fn trim_eol(s: &str) -> &str {
+    let s = s.strip_suffix('\n').unwrap_or(s);
+    s.strip_suffix('\r').unwrap_or(s)
+}
+
+impl<R: BufRead> LineReader<R> {
+    pub fn new(reader: R) -> LineReader<R> {
+        LineReader {
+            reader,
+            line: String::new(),
+            block: String::new(),
+        }
+    }
+
+    pub fn next_line(&mut self) -> io::Result<Option<&str>> {
+        self.line.clear();
+        if self.reader.read_line(&mut self.line)? == 0 {
+            return Ok(None);
+        }
+        Ok(Some(trim_eol(&self.line)))
+    }
+
+    /// Returns the next run of non-blank lines joined with `\n`, skipping
+    /// any blank lines before it.
+    pub fn next_block(&mut self) -> io::Result<Option<&str>> {
+        self.block.clear();
+        loop {
+            self.line.clear();
+            if self.reader.read_line(&mut self.line)? == 0 {
+                break;
+            }
+
+            let line = trim_eol(&self.line);
+            if line.trim().is_empty() {
+                if self.block.is_empty() {
+                    continue;
+                }
+                break;
+            }
+
+            if !self.block.is_empty() {
+                self.block.push('\n');
+            }
+            self.block.push_str(line);
+        }
+
+        if self.block.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(&self.block))
+        }
+    }
+}
+
+/// Opens `path` for line reading, or stdin if `path` is `-`.
+pub fn open_input(path: &Path) -> io::Result<LineReader<Box<dyn BufRead>>> {
+    let reader: Box<dyn BufRead> = if path == Path::new("-") {
+        Box::new(io::stdin().lock())
+    } else {
+        Box::new(BufReader::new(File::open(path)?))
+    };
+    Ok(LineReader::new(reader))
+}
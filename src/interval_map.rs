@@ -0,0 +1,227 @@
+use std::{error, fmt, ops::Range};
+
+use num::PrimInt;
+
+/// Maps `start..end` onto `dst..dst + (end - start)`. Pieces only come out
+/// of `IntervalMap::insert`, which makes sure the destination range fits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Piece<T> {
+    pub start: T,
+    pub end: T,
+    pub dst: T,
+}
+
+impl<T: PrimInt> Piece<T> {
+    pub fn apply(&self, x: T) -> T {
+        self.dst + (x - self.start)
+    }
+}
+
+/// Why a piece couldn't be inserted: its source range overlaps an existing
+/// piece, or its source or destination range runs past the end of `T`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InsertError<T> {
+    Overlap { existing: Range<T>, new: Range<T> },
+    Overflow { src: T, len: T, dst: T },
+}
+
+impl<T: fmt::Debug> fmt::Display for InsertError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InsertError::Overlap { existing, new } => {
+                write!(f, "source range {new:?} overlaps {existing:?}")
+            }
+            InsertError::Overflow { src, len, dst } => write!(
+                f,
+                "mapping {len:?} values from {src:?} to {dst:?} runs out of range"
+            ),
+        }
+    }
+}
+
+impl<T: fmt::Debug> error::Error for InsertError<T> {}
+
+/// A piecewise-offset mapping. Pieces are kept sorted by `start` and don't
+/// overlap; anything outside of them maps to itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntervalMap<T> {
+    pieces: Vec<Piece<T>>,
+}
+
+impl<T: PrimInt> Default for IntervalMap<T> {
+    fn default() -> Self {
+        IntervalMap { pieces: vec![] }
+    }
+}
+
+/// Sorts `ranges` and merges any that overlap or touch, dropping empty ones.
+pub fn normalize<T: PrimInt>(mut ranges: Vec<Range<T>>) -> Vec<Range<T>> {
+    ranges.retain(|r| r.start < r.end);
+    ranges.sort_by_key(|r| r.start);
+
+    let mut merged: Vec<Range<T>> = vec![];
+    for r in ranges {
+        match merged.last_mut() {
+            Some(last) if r.start <= last.end => last.end = last.end.max(r.end),
+            _ => merged.push(r),
+        }
+    }
+    merged
+}
+
+impl<T: PrimInt> IntervalMap<T> {
+    pub fn new() -> IntervalMap<T> {
+        IntervalMap::default()
+    }
+
+    pub fn pieces(&self) -> &[Piece<T>] {
+        &self.pieces
+    }
+
+    /// Maps `src..src + len` onto `dst..dst + len`, refusing sources that
+    /// overlap a piece already in the map and ranges that don't fit in `T`.
+    pub fn insert(&mut self, src: T, len: T, dst: T) -> Result<(), InsertError<T>> {
+        if len.is_zero() {
+            return Ok(());
+        }
+        let (Some(end), Some(_)) = (src.checked_add(&len), dst.checked_add(&len)) else {
+            return Err(InsertError::Overflow { src, len, dst });
+        };
+        let piece = Piece {
+            start: src,
+            end,
+            dst,
+        };
+        let idx = self.pieces.partition_point(|p| p.start < src);
+        let neighbours = [idx.checked_sub(1), Some(idx)];
+        for p in neighbours
+            .into_iter()
+            .flatten()
+            .filter_map(|i| self.pieces.get(i))
+        {
+            if p.start < piece.end && piece.start < p.end {
+                return Err(InsertError::Overlap {
+                    existing: p.start..p.end,
+                    new: piece.start..piece.end,
+                });
+            }
+        }
+        self.pieces.insert(idx, piece);
+        Ok(())
+    }
+
+    pub fn get(&self, x: T) -> T {
+        let idx = self.pieces.partition_point(|p| p.end <= x);
+        match self.pieces.get(idx) {
+            Some(p) if p.start <= x => p.apply(x),
+            _ => x,
+        }
+    }
+
+    /// Splits `r` along piece boundaries, including the identity gaps
+    /// between pieces, and returns each part with the piece that maps it.
+    fn split(&self, r: Range<T>) -> Vec<Piece<T>> {
+        let mut parts = vec![];
+        let mut at = r.start;
+        let idx = self.pieces.partition_point(|p| p.end <= at);
+        for p in self.pieces[idx..].iter() {
+            if at >= r.end {
+                break;
+            }
+            if at < p.start {
+                let end = p.start.min(r.end);
+                parts.push(Piece {
+                    start: at,
+                    end,
+                    dst: at,
+                });
+                at = end;
+            }
+            if at < r.end {
+                let end = p.end.min(r.end);
+                parts.push(Piece {
+                    start: at,
+                    end,
+                    dst: p.apply(at),
+                });
+                at = end;
+            }
+        }
+        if at < r.end {
+            parts.push(Piece {
+                start: at,
+                end: r.end,
+                dst: at,
+            });
+        }
+        parts
+    }
+
+    pub fn map_range(&self, r: Range<T>) -> Vec<Range<T>> {
+        self.split(r)
+            .into_iter()
+            .map(|p| p.dst..p.dst + (p.end - p.start))
+            .collect()
+    }
+
+    /// Maps every range in `ranges`, returning the normalized image.
+    pub fn map_ranges(&self, ranges: &[Range<T>]) -> Vec<Range<T>> {
+        normalize(
+            ranges
+                .iter()
+                .flat_map(|r| self.map_range(r.clone()))
+                .collect(),
+        )
+    }
+
+    /// Every input that maps into `r`, normalized.
+    pub fn preimage(&self, r: Range<T>) -> Vec<Range<T>> {
+        let ranges = self
+            .split(T::min_value()..T::max_value())
+            .into_iter()
+            .filter_map(|p| {
+                let dst_end = p.dst + (p.end - p.start);
+                let lo = p.dst.max(r.start);
+                let hi = dst_end.min(r.end);
+                (lo < hi).then(|| p.start + (lo - p.dst)..p.start + (hi - p.dst))
+            })
+            .collect();
+        normalize(ranges)
+    }
+
+    /// Every input that maps into any of `ranges`, normalized.
+    pub fn preimage_ranges(&self, ranges: &[Range<T>]) -> Vec<Range<T>> {
+        normalize(
+            ranges
+                .iter()
+                .flat_map(|r| self.preimage(r.clone()))
+                .collect(),
+        )
+    }
+
+    /// A single map equivalent to applying `self` and then `next`.
+    pub fn compose(&self, next: &IntervalMap<T>) -> IntervalMap<T> {
+        let mut pieces: Vec<Piece<T>> = vec![];
+        for outer in self.split(T::min_value()..T::max_value()) {
+            let image = outer.dst..outer.dst + (outer.end - outer.start);
+            for inner in next.split(image) {
+                let start = outer.start + (inner.start - outer.dst);
+                let end = start + (inner.end - inner.start);
+                if start == inner.dst {
+                    continue;
+                }
+                match pieces.last_mut() {
+                    Some(last) if last.end == start && last.apply(start) == inner.dst => {
+                        last.end = end
+                    }
+                    _ => pieces.push(Piece {
+                        start,
+                        end,
+                        dst: inner.dst,
+                    }),
+                }
+            }
+        }
+        IntervalMap { pieces }
+    }
+}
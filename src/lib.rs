@@ -1,18 +1,34 @@
 pub use clap::Parser;
-use std::fs::File;
+pub use input::{open_input, LineReader};
 use std::io::{self, prelude::*};
 use std::path::{Path, PathBuf};
 
-pub fn read_as_lines(path: &Path) -> io::Result<Vec<String>> {
-    let mut file = File::open(path)?;
-
-    let mut buf = String::new();
-    file.read_to_string(&mut buf)?;
+pub mod day1;
+pub mod day11;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day22;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day9;
+pub mod input;
+pub mod interval_map;
+pub mod sections;
+pub mod svg;
 
-    let mut lines = buf.split("\n").map(|s| s.to_owned()).collect::<Vec<_>>();
+pub fn read_as_lines(path: &Path) -> io::Result<Vec<String>> {
+    let mut reader = open_input(path)?;
 
-    if lines.last().map_or(false, |v| v == "") {
-        lines.remove(lines.len() - 1);
+    let mut lines = vec![];
+    while let Some(line) = reader.next_line()? {
+        lines.push(line.to_owned());
     }
 
     Ok(lines)
@@ -31,6 +47,7 @@ pub fn read_line() -> io::Result<()> {
 
 #[derive(Parser, Debug)]
 pub struct Args {
+    /// input file, or `-` to read from stdin
     #[arg(short, long)]
     pub file: PathBuf,
 }
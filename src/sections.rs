@@ -0,0 +1,181 @@
+use std::{error, fmt, str::FromStr};
+
+use nom::IResult;
+
+use crate::input::trim_eol;
+
+/// Splits puzzle input into blank-line separated sections. A section whose
+/// first line is a bare `name:` header is named, and its body is everything
+/// after the header; other sections are only reachable by position.
+#[derive(Debug)]
+pub struct Sections<'a> {
+    sections: Vec<Section<'a>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Section<'a> {
+    pub index: usize,
+    pub name: Option<&'a str>,
+    pub body: &'a str,
+}
+
+#[derive(Debug)]
+pub enum SectionError {
+    Missing(String),
+    Parse {
+        section: String,
+        line: Option<usize>,
+        message: String,
+    },
+}
+
+impl fmt::Display for SectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SectionError::Missing(section) => write!(f, "missing {section}"),
+            SectionError::Parse {
+                section,
+                line: Some(line),
+                message,
+            } => write!(f, "{section}, line {line}: {message}"),
+            SectionError::Parse {
+                section,
+                line: None,
+                message,
+            } => write!(f, "{section}: {message}"),
+        }
+    }
+}
+
+impl error::Error for SectionError {}
+
+impl<'a> Sections<'a> {
+    pub fn new(input: &'a str) -> Sections<'a> {
+        let mut sections = vec![];
+        let mut block: Option<(usize, usize)> = None;
+        let mut offset = 0;
+
+        let mut push = |(start, end): (usize, usize)| {
+            let text = &input[start..end];
+            let (first, rest) = text.split_once('\n').unwrap_or((text, ""));
+            let header = trim_eol(first).trim_end();
+            let (name, body) = match header.strip_suffix(':') {
+                Some(name) if !name.contains(':') => (Some(name), rest),
+                _ => (None, text),
+            };
+            sections.push(Section {
+                index: sections.len(),
+                name,
+                body,
+            });
+        };
+
+        for line in input.split_inclusive('\n') {
+            let content = trim_eol(line);
+            if content.trim().is_empty() {
+                if let Some(b) = block.take() {
+                    push(b);
+                }
+            } else {
+                let end = offset + content.len();
+                block = Some(block.map_or((offset, end), |(start, _)| (start, end)));
+            }
+            offset += line.len();
+        }
+        if let Some(b) = block {
+            push(b);
+        }
+
+        Sections { sections }
+    }
+
+    pub fn len(&self) -> usize {
+        self.sections.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sections.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Section<'a>> {
+        self.sections.iter()
+    }
+
+    pub fn get(&self, index: usize) -> Result<&Section<'a>, SectionError> {
+        self.sections
+            .get(index)
+            .ok_or_else(|| SectionError::Missing(format!("section {index}")))
+    }
+
+    pub fn named(&self, name: &str) -> Result<&Section<'a>, SectionError> {
+        self.sections
+            .iter()
+            .find(|s| s.name == Some(name))
+            .ok_or_else(|| SectionError::Missing(format!("section '{name}'")))
+    }
+}
+
+impl<'a> Section<'a> {
+    /// How the section is referred to in errors.
+    pub fn label(&self) -> String {
+        match self.name {
+            Some(name) => format!("section '{name}'"),
+            None => format!("section {}", self.index),
+        }
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = &'a str> {
+        self.body.lines()
+    }
+
+    fn error(&self, line: Option<usize>, message: String) -> SectionError {
+        SectionError::Parse {
+            section: self.label(),
+            line,
+            message,
+        }
+    }
+
+    /// Runs a nom parser over the body, which must consume all of it apart
+    /// from trailing whitespace.
+    pub fn parse_with<T, P>(&self, mut parser: P) -> Result<T, SectionError>
+    where
+        P: FnMut(&'a str) -> IResult<&'a str, T>,
+    {
+        match parser(self.body) {
+            Ok((rest, value)) if rest.trim().is_empty() => Ok(value),
+            Ok((rest, _)) => {
+                let consumed = &self.body[..self.body.len() - rest.len()];
+                let line = consumed.matches('\n').count() + 1;
+                Err(self.error(Some(line), format!("unparsed input {rest:?}")))
+            }
+            Err(err) => Err(self.error(None, err.map_input(|s| s.to_string()).to_string())),
+        }
+    }
+
+    pub fn parse<T>(&self) -> Result<T, SectionError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        self.body
+            .trim()
+            .parse()
+            .map_err(|err: T::Err| self.error(None, err.to_string()))
+    }
+
+    /// Parses every line of the body, reporting the line number on failure.
+    pub fn parse_lines<T>(&self) -> Result<Vec<T>, SectionError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        self.lines()
+            .enumerate()
+            .map(|(n, line)| {
+                line.parse()
+                    .map_err(|err: T::Err| self.error(Some(n + 1), err.to_string()))
+            })
+            .collect()
+    }
+}
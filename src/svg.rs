@@ -0,0 +1,155 @@
+use std::fmt::Write;
+
+use itertools::Itertools;
+
+/// Drops points that sit on a straight line between their neighbours, so a
+/// path walked one cell at a time becomes just its corners.
+pub fn corners(path: &[(i64, i64)]) -> Vec<(i64, i64)> {
+    let n = path.len();
+    (0..n)
+        .filter(|&i| {
+            let (prev, at, next) = (path[(i + n - 1) % n], path[i], path[(i + 1) % n]);
+            n < 3 || (prev.0 != next.0 && prev.1 != next.1) || at == prev || at == next
+        })
+        .map(|i| path[i])
+        .collect()
+}
+
+/// Renders a closed rectilinear polygon as SVG, scaled so its longest side
+/// fits in `size` pixels however large the coordinates are.
+#[derive(Debug, Clone)]
+pub struct PolygonSvg {
+    vertices: Vec<(i64, i64)>,
+    size: f64,
+    stroke: String,
+    edge_colors: Vec<String>,
+    fill: Option<String>,
+    markers: bool,
+}
+
+impl PolygonSvg {
+    /// `vertices` are `(x, y)` with `y` growing downwards. The polygon is
+    /// closed automatically and a repeated first vertex at the end is fine.
+    pub fn new(vertices: &[(i64, i64)]) -> PolygonSvg {
+        let mut vertices = vertices.to_vec();
+        if vertices.len() > 1 && vertices.first() == vertices.last() {
+            vertices.pop();
+        }
+        PolygonSvg {
+            vertices,
+            size: 800.0,
+            stroke: "black".to_string(),
+            edge_colors: vec![],
+            fill: None,
+            markers: false,
+        }
+    }
+
+    pub fn size(mut self, size: f64) -> PolygonSvg {
+        self.size = size;
+        self
+    }
+
+    pub fn stroke(mut self, color: &str) -> PolygonSvg {
+        self.stroke = color.to_string();
+        self
+    }
+
+    /// Colors for each edge in turn, starting with the one leaving the
+    /// first vertex; edges without one use the stroke color.
+    pub fn edge_colors(mut self, colors: Vec<String>) -> PolygonSvg {
+        self.edge_colors = colors;
+        self
+    }
+
+    /// Fills the interior of the polygon.
+    pub fn fill(mut self, color: &str) -> PolygonSvg {
+        self.fill = Some(color.to_string());
+        self
+    }
+
+    /// Marks every vertex with a dot.
+    pub fn markers(mut self, markers: bool) -> PolygonSvg {
+        self.markers = markers;
+        self
+    }
+
+    pub fn render(&self) -> String {
+        let (min_x, max_x) = self
+            .vertices
+            .iter()
+            .map(|v| v.0)
+            .minmax()
+            .into_option()
+            .unwrap_or((0, 0));
+        let (min_y, max_y) = self
+            .vertices
+            .iter()
+            .map(|v| v.1)
+            .minmax()
+            .into_option()
+            .unwrap_or((0, 0));
+        let span = (max_x - min_x).max(max_y - min_y).max(1) as f64;
+        let margin = 10.0;
+        let scale = (self.size - 2.0 * margin) / span;
+        let px = |&(x, y): &(i64, i64)| {
+            (
+                margin + (x - min_x) as f64 * scale,
+                margin + (y - min_y) as f64 * scale,
+            )
+        };
+
+        let width = 2.0 * margin + (max_x - min_x) as f64 * scale;
+        let height = 2.0 * margin + (max_y - min_y) as f64 * scale;
+        let points = self.vertices.iter().map(px).collect_vec();
+
+        let mut svg = String::new();
+        writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width:.0}" height="{height:.0}">"#
+        )
+        .unwrap();
+
+        let outline = points
+            .iter()
+            .map(|(x, y)| format!("{x:.2},{y:.2}"))
+            .join(" ");
+        let fill = self.fill.as_deref().unwrap_or("none");
+        if self.edge_colors.is_empty() {
+            writeln!(
+                svg,
+                r#"  <polygon points="{outline}" fill="{fill}" stroke="{}" stroke-width="1"/>"#,
+                self.stroke
+            )
+            .unwrap();
+        } else {
+            writeln!(
+                svg,
+                r#"  <polygon points="{outline}" fill="{fill}" stroke="none"/>"#
+            )
+            .unwrap();
+            for (n, (a, b)) in points.iter().circular_tuple_windows().enumerate() {
+                let color = self.edge_colors.get(n).unwrap_or(&self.stroke);
+                writeln!(
+                    svg,
+                    r#"  <line x1="{:.2}" y1="{:.2}" x2="{:.2}" y2="{:.2}" stroke="{color}" stroke-width="2" stroke-linecap="square"/>"#,
+                    a.0, a.1, b.0, b.1
+                )
+                .unwrap();
+            }
+        }
+
+        if self.markers {
+            for (x, y) in points.iter() {
+                writeln!(
+                    svg,
+                    r#"  <circle cx="{x:.2}" cy="{y:.2}" r="2" fill="red"/>"#
+                )
+                .unwrap();
+            }
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+}